@@ -13,6 +13,29 @@ pub fn generate(c: &mut Criterion) {
     });
 }
 
+fn box_muller(c: &mut Criterion) {
+    let dist = Normal::new(0., 1.);
+    c.bench_function("generate 1e6 normals with uncached box-muller", |b| {
+        b.iter(|| {
+            (0..1e6 as usize)
+                .map(|_| dist.sample_pair().0)
+                .collect::<Vec<_>>()
+        })
+    });
+    c.bench_function("generate 1e6 normals with cached box-muller", |b| {
+        b.iter(|| (0..1e6 as usize).map(|_| dist.sample()).collect::<Vec<_>>())
+    });
+}
+
+fn gamma(c: &mut Criterion) {
+    c.bench_function("generate 1e6 gammas with small shape", |b| {
+        b.iter(|| Gamma::new(1.5, 1.).sample_n(1e6 as usize))
+    });
+    c.bench_function("generate 1e6 gammas with large shape", |b| {
+        b.iter(|| Gamma::new(20., 1.).sample_n(1e6 as usize))
+    });
+}
+
 fn discrete_uniform(c: &mut Criterion) {
     c.bench_function("generate 1e6 discrete uniform values by rounding", |b| {
         b.iter(|| {
@@ -52,5 +75,5 @@ fn t(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, t);
+criterion_group!(benches, t, box_muller, gamma);
 criterion_main!(benches);
@@ -165,6 +165,31 @@ pub fn criterion_solve(c: &mut Criterion) {
     c.bench_function("30 variable linear solve", |b| b.iter(|| solve(&a30, &b30)));
 }
 
+pub fn criterion_solve_tridiagonal(c: &mut Criterion) {
+    // a diagonally dominant tridiagonal system, solved with the Thomas algorithm and densely
+    for &n in &[10, 100, 400] {
+        let lower = Uniform::new(-1., 1.).sample_n(n - 1);
+        let upper = Uniform::new(-1., 1.).sample_n(n - 1);
+        let diag = Uniform::new(3., 5.).sample_n(n);
+        let rhs = Uniform::new(-10., 10.).sample_n(n);
+        let mut dense = vec![0.; n * n];
+        for i in 0..n {
+            dense[i * n + i] = diag[i];
+            if i > 0 {
+                dense[i * n + i - 1] = lower[i - 1];
+                dense[(i - 1) * n + i] = upper[i - 1];
+            }
+        }
+
+        c.bench_function(&format!("{} variable tridiagonal solve", n), |b| {
+            b.iter(|| solve_tridiagonal(&lower, &diag, &upper, &rhs))
+        });
+        c.bench_function(&format!("{} variable dense tridiagonal solve", n), |b| {
+            b.iter(|| solve(&dense, &rhs))
+        });
+    }
+}
+
 pub fn criterion_invert(c: &mut Criterion) {
     let a5 = Uniform::new(2., 50.).sample_n(5 * 5);
     let a10 = Uniform::new(2., 50.).sample_n(10 * 10);
@@ -194,6 +219,43 @@ pub fn criterion_matmul(c: &mut Criterion) {
     });
 }
 
+pub fn criterion_matmul_tiled_vs_naive(c: &mut Criterion) {
+    fn naive(a: &[f64], b: &[f64], m: usize, l: usize, n: usize) -> Vec<f64> {
+        let mut c = vec![0.; m * n];
+        for i in 0..m {
+            for k in 0..l {
+                let temp = a[i * l + k];
+                for j in 0..n {
+                    c[i * n + j] += temp * b[k * n + j];
+                }
+            }
+        }
+        c
+    }
+
+    let normgen = Normal::new(2., 50.);
+    let a = normgen.sample_n(512 * 512);
+    let b = normgen.sample_n(512 * 512);
+
+    c.bench_function("512x512 naive matmul", |bench| {
+        bench.iter(|| naive(&a, &b, 512, 512, 512))
+    });
+    c.bench_function("512x512 tiled matmul", |bench| {
+        bench.iter(|| matmul(&a, &b, 512, 512, false, false))
+    });
+}
+
+pub fn criterion_matmul_xtwx(c: &mut Criterion) {
+    // the X^T W X product computed at each GLM iteration, with 2000 observations and 200 predictors
+    let normgen = Normal::new(0., 1.);
+    let x = normgen.sample_n(2000 * 200);
+    let wx = normgen.sample_n(2000 * 200);
+
+    c.bench_function("2000x200 xtwx matmul", |b| {
+        b.iter(|| matmul(&x, &wx, 2000, 2000, true, false))
+    });
+}
+
 pub fn criterion_matmul_blocked(c: &mut Criterion) {
     let normgen = Normal::new(2., 50.);
     let a3 = normgen.sample_n(512 * 512);
@@ -214,5 +276,11 @@ pub fn criterion_xtx(c: &mut Criterion) {
     c.bench_function("20x6 xtx", |b| b.iter(|| xtx(&a_20_6, 20)));
 }
 
-criterion_group!(benches, criterion_dot);
+criterion_group!(
+    benches,
+    criterion_dot,
+    criterion_matmul_xtwx,
+    criterion_matmul_tiled_vs_naive,
+    criterion_solve_tridiagonal
+);
 criterion_main!(benches);
@@ -3,6 +3,7 @@ use crate::distributions::*;
 
 /// Implements the [Bernoulli distribution](https://en.wikipedia.org/wiki/Bernoulli_distribution).
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bernoulli {
     /// Probability `p` of the Bernoulli distribution
     p: f64,
@@ -14,10 +15,18 @@ impl Bernoulli {
     /// # Errors
     /// Panics if p is not in [0, 1].
     pub fn new(p: f64) -> Self {
+        Self::try_new(p).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new Bernoulli distribution with probability `p`.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if p is not in [0, 1].
+    pub fn try_new(p: f64) -> Result<Self, DistError> {
         if !(0. ..=1.).contains(&p) {
-            panic!("`p` must be in [0, 1].");
+            return Err(DistError("`p` must be in [0, 1]."));
         }
-        Bernoulli { p }
+        Ok(Bernoulli { p })
     }
     pub fn set_p(&mut self, p: f64) -> &mut Self {
         if !(0. ..=1.).contains(&p) {
@@ -50,6 +59,10 @@ impl Distribution for Bernoulli {
             0.
         }
     }
+    /// Returns the support of the distribution, which is `{0, 1}`.
+    fn support(&self) -> (f64, f64) {
+        (0., 1.)
+    }
 }
 
 impl Distribution1D for Bernoulli {
@@ -90,6 +103,17 @@ impl Variance for Bernoulli {
     }
 }
 
+impl Entropy for Bernoulli {
+    /// Calculates the entropy, given by `-p ln(p) - q ln(q)`, where `0 ln(0)` is taken to be `0`.
+    fn entropy(&self) -> f64 {
+        [self.p, 1. - self.p]
+            .iter()
+            .filter(|&&p| p > 0.)
+            .map(|p| -p * p.ln())
+            .sum()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,4 +131,16 @@ mod tests {
         assert!(Bernoulli::default().pmf(2) == 0.);
         assert!(Bernoulli::default().pmf(0) == 0.5);
     }
+
+    #[test]
+    fn test_entropy() {
+        assert_eq!(Bernoulli::new(0.).entropy(), 0.);
+        assert_eq!(Bernoulli::new(1.).entropy(), 0.);
+        assert_approx_eq!(Bernoulli::new(0.5).entropy(), 2_f64.ln());
+        for i in 1..100 {
+            let h = Bernoulli::new(i as f64 / 100.).entropy();
+            assert!(h <= Bernoulli::new(0.5).entropy());
+            assert_approx_eq!(h, Bernoulli::new(1. - i as f64 / 100.).entropy());
+        }
+    }
 }
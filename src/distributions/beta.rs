@@ -1,8 +1,10 @@
 use crate::distributions::*;
 use crate::functions::beta;
+use crate::statistics::{mean, sample_var};
 
 /// Implements the [Beta](https://en.wikipedia.org/wiki/Beta_distribution) distribution.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Beta {
     /// Shape parameter α.
     alpha: f64,
@@ -22,15 +24,23 @@ impl Beta {
     /// # Errors
     /// Panics if `alpha <= 0` or `beta <= 0`.
     pub fn new(alpha: f64, beta: f64) -> Self {
+        Self::try_new(alpha, beta).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new Beta distribution with parameters `alpha` and `beta`.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if `alpha <= 0` or `beta <= 0`.
+    pub fn try_new(alpha: f64, beta: f64) -> Result<Self, DistError> {
         if alpha <= 0. || beta <= 0. {
-            panic!("Both alpha and beta must be positive.");
+            return Err(DistError("Both alpha and beta must be positive."));
         }
-        Beta {
+        Ok(Beta {
             alpha,
             beta,
             alpha_gen: Gamma::new(alpha, 1.),
             beta_gen: Gamma::new(beta, 1.),
-        }
+        })
     }
     pub fn set_alpha(&mut self, alpha: f64) -> &mut Self {
         if alpha <= 0. {
@@ -50,6 +60,32 @@ impl Beta {
     }
 }
 
+impl Fit for Beta {
+    const NPARAMS: usize = 2;
+    /// Fit a Beta distribution to the data using the method of moments, matching the sample mean
+    /// `m` and sample variance `v`. The parameters are `alpha = m * c` and `beta = (1 - m) * c`,
+    /// where `c = m * (1 - m) / v - 1`.
+    ///
+    /// # Errors
+    /// Panics if there are fewer than two data points, if any data point is not in `(0, 1)`, or if
+    /// the sample variance is not below `m * (1 - m)`.
+    fn fit(data: &[f64]) -> Self {
+        assert!(data.len() > 1, "Need at least two data points.");
+        assert!(
+            data.iter().all(|&x| x > 0. && x < 1.),
+            "Data must be in (0, 1)."
+        );
+        let m = mean(data);
+        let v = sample_var(data);
+        assert!(
+            v > 0. && v < m * (1. - m),
+            "Sample variance must be positive and less than mean * (1 - mean)."
+        );
+        let c = m * (1. - m) / v - 1.;
+        Self::new(m * c, (1. - m) * c)
+    }
+}
+
 impl Default for Beta {
     fn default() -> Self {
         Self::new(1., 1.)
@@ -63,6 +99,10 @@ impl Distribution for Beta {
         let x = self.alpha_gen.sample();
         x / (x + self.beta_gen.sample())
     }
+    /// Returns the support of the distribution, which is `[0, 1]`.
+    fn support(&self) -> (f64, f64) {
+        (0., 1.)
+    }
 }
 
 impl Distribution1D for Beta {
@@ -106,7 +146,7 @@ impl Variance for Beta {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::statistics::{mean, var};
+    use crate::statistics::var;
     use approx_eq::assert_approx_eq;
 
     #[test]
@@ -116,4 +156,19 @@ mod tests {
         assert_approx_eq!(dist.mean(), mean(&data), 1e-2);
         assert_approx_eq!(dist.var(), var(&data), 1e-2);
     }
+
+    #[test]
+    fn test_fit() {
+        alea::set_seed(11);
+        let data = Beta::new(2., 5.).sample_n(1e5 as usize);
+        let fitted = Beta::fit(&data);
+        assert_approx_eq!(fitted.alpha, 2., 3e-2);
+        assert_approx_eq!(fitted.beta, 5., 3e-2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fit_out_of_range() {
+        Beta::fit(&[0.2, 0.5, 1.3, 0.7]);
+    }
 }
@@ -1,9 +1,10 @@
 use crate::distributions::*;
-use crate::functions::binom_coeff;
+use crate::functions::ln_choose;
 
 /// Implements the [Binomial](https://en.wikipedia.org/wiki/https://en.wikipedia.org/wiki/Binomial_distribution)
 /// distribution with trials `n` and probability of success `p`.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Binomial {
     n: u64,
     p: f64,
@@ -15,10 +16,18 @@ impl Binomial {
     /// # Remarks
     /// `n` must be a non-negative integer, and `p` must be in [0, 1].
     pub fn new(n: u64, p: f64) -> Self {
+        Self::try_new(n, p).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new Binomial distribution with parameters `n` and `p`.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if `p` is not in [0, 1].
+    pub fn try_new(n: u64, p: f64) -> Result<Self, DistError> {
         if !(0. ..=1.).contains(&p) {
-            panic!("`p` must be in [0, 1]");
+            return Err(DistError("`p` must be in [0, 1]"));
         }
-        Binomial { n, p }
+        Ok(Binomial { n, p })
     }
     pub fn set_n(&mut self, n: u64) -> &mut Self {
         self.n = n;
@@ -60,6 +69,10 @@ impl Distribution for Binomial {
 
         res as f64
     }
+    /// Returns the support of the distribution, which is the integers in `[0, n]`.
+    fn support(&self) -> (f64, f64) {
+        (0., self.n as f64)
+    }
 }
 
 impl Distribution1D for Binomial {
@@ -259,12 +272,22 @@ pub fn binomial_btpe(n: u64, p: f64) -> u64 {
 impl Discrete for Binomial {
     /// Calculates the [probability mass
     /// function](https://en.wikipedia.org/wiki/Probability_mass_function) for the given Binomial
-    /// distribution at `k`.
-    ///
+    /// distribution at `k`. The computation is done in log space, so it does not overflow for
+    /// large `n`.
     fn pmf(&self, k: i64) -> f64 {
-        binom_coeff(self.n, k as u64) as f64
-            * self.p.powi(k as i32)
-            * (1. - self.p).powi((self.n - k as u64) as i32)
+        if k < 0 || k as u64 > self.n {
+            return 0.;
+        }
+        let k = k as u64;
+        let nk = self.n - k;
+        // take 0 * ln(0) to be 0 so that p = 0 and p = 1 are handled correctly
+        let successes = if k == 0 { 0. } else { k as f64 * self.p.ln() };
+        let failures = if nk == 0 {
+            0.
+        } else {
+            nk as f64 * (1. - self.p).ln()
+        };
+        (ln_choose(self.n, k) + successes + failures).exp()
     }
 }
 
@@ -306,4 +329,20 @@ mod tests {
         assert_approx_eq!(mean2, 35., 1e-2);
         assert_approx_eq!(var2, 17.5, 1e-2);
     }
+
+    #[test]
+    fn test_pmf() {
+        assert_approx_eq!(Binomial::new(20, 0.3).pmf(7), 0.1642619852172363);
+        assert_eq!(Binomial::new(20, 0.3).pmf(-1), 0.);
+        assert_eq!(Binomial::new(20, 0.3).pmf(21), 0.);
+        assert_eq!(Binomial::new(5, 0.).pmf(0), 1.);
+        assert_eq!(Binomial::new(5, 1.).pmf(5), 1.);
+
+        let distr = Binomial::new(1000, 0.5);
+        assert!(distr.pmf(500).is_finite());
+        assert_approx_eq!(distr.pmf(500), 0.0252250181783608);
+
+        let distr = Binomial::new(5000, 0.37);
+        assert_approx_eq!((0..=5000).map(|k| distr.pmf(k)).sum::<f64>(), 1.);
+    }
 }
@@ -1,8 +1,9 @@
 use crate::distributions::*;
-use crate::functions::gamma;
+use crate::functions::{gamma, gamma_inc};
 
 /// Implements the [Chi square](https://en.wikipedia.org/wiki/Chi-square_distribution) distribution.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChiSquared {
     /// Degrees of freedom (k)
     dof: usize,
@@ -10,16 +11,34 @@ pub struct ChiSquared {
 }
 
 impl ChiSquared {
-    /// Create a new Chi square distribution with
+    /// Create a new Chi square distribution with `dof` degrees of freedom.
     ///
     /// # Errors
     /// Panics if degrees of freedom is not positive.
     pub fn new(dof: usize) -> Self {
-        assert!(dof > 0, "Degrees of freedom must be positive.");
-        ChiSquared {
+        Self::try_new(dof).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new Chi square distribution with `dof` degrees of freedom.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if degrees of freedom is not positive.
+    pub fn try_new(dof: usize) -> Result<Self, DistError> {
+        if dof == 0 {
+            return Err(DistError("Degrees of freedom must be positive."));
+        }
+        Ok(ChiSquared {
             dof,
             sampler: Gamma::new((dof as f64) / 2., 0.5),
+        })
+    }
+    /// Calculates the cumulative distribution function at `x`, using the [regularized incomplete
+    /// gamma function](crate::functions::gamma_inc).
+    pub fn cdf(&self, x: f64) -> f64 {
+        if x <= 0. {
+            return 0.;
         }
+        gamma_inc(self.dof as f64 / 2., x / 2.)
     }
     pub fn set_dof(&mut self, dof: usize) -> &mut Self {
         assert!(dof > 0, "Degrees of freedom must be positive.");
@@ -40,6 +59,10 @@ impl Distribution for ChiSquared {
     fn sample(&self) -> f64 {
         self.sampler.sample()
     }
+    /// Returns the support of the distribution, which is `[0, inf)`.
+    fn support(&self) -> (f64, f64) {
+        (0., f64::INFINITY)
+    }
 }
 
 impl Distribution1D for ChiSquared {
@@ -98,4 +121,18 @@ mod tests {
         assert_approx_eq!(5., mean(&data2), 1e-2);
         assert_approx_eq!(10., var(&data2), 1e-2);
     }
+
+    #[test]
+    fn test_cdf() {
+        let dist = ChiSquared::new(2);
+        for &x in &[0.1, 1., 2.5, 10.] {
+            assert_approx_eq!(dist.cdf(x), 1. - (-x / 2_f64).exp(), 1e-12);
+        }
+        assert_eq!(dist.cdf(-1.), 0.);
+        assert_approx_eq!(ChiSquared::new(1).cdf(3.841458820694124), 0.95, 1e-10);
+        let dist = ChiSquared::new(5);
+        for &x in &[0.5, 3., 7., 15.] {
+            assert!((dist.cdf(x) - dist.cdf_numerical(x)).abs() < 1e-8);
+        }
+    }
 }
@@ -0,0 +1,173 @@
+use super::{Continuous, DistError, Distribution, DistributionND, Gamma, Mean};
+use crate::functions::lgamma;
+use crate::prelude::Vector;
+
+/// Implements the [Dirichlet](https://en.wikipedia.org/wiki/Dirichlet_distribution) distribution,
+/// a distribution over the probability simplex (vectors of nonnegative values that sum to 1).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dirichlet {
+    /// Concentration parameters α.
+    alpha: Vector,
+    /// Gamma(alpha_i, 1) distributions used to sample gamma variables, which are normalized to
+    /// create Dirichlet variables.
+    gens: Vec<Gamma>,
+}
+
+impl Dirichlet {
+    /// Create a new Dirichlet distribution with concentration parameters `alpha`.
+    ///
+    /// # Errors
+    /// Panics if there are fewer than two concentration parameters or if any of them are not
+    /// positive.
+    pub fn new<V: Into<Vector>>(alpha: V) -> Self {
+        Self::try_new(alpha).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new Dirichlet distribution with concentration parameters `alpha`.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than two concentration parameters or if any of them are
+    /// not positive.
+    pub fn try_new<V: Into<Vector>>(alpha: V) -> Result<Self, DistError> {
+        let alpha = alpha.into();
+        if alpha.len() < 2 {
+            return Err(DistError("Need at least two concentration parameters."));
+        }
+        if !alpha.iter().all(|&a| a > 0.) {
+            return Err(DistError("Concentration parameters must be positive."));
+        }
+        let gens = alpha.iter().map(|&a| Gamma::new(a, 1.)).collect();
+        Ok(Dirichlet { alpha, gens })
+    }
+    /// Returns the concentration parameters.
+    pub fn alpha(&self) -> &[f64] {
+        &self.alpha
+    }
+}
+
+impl Distribution for Dirichlet {
+    type Output = Vector;
+    /// Samples from the given Dirichlet distribution by normalizing independent Gamma(alpha_i, 1)
+    /// variables.
+    fn sample(&self) -> Vector {
+        let x = self.gens.iter().map(|g| g.sample()).collect::<Vector>();
+        let total = x.sum();
+        x.iter().map(|xi| xi / total).collect()
+    }
+    /// Returns the support of each component, which is `[0, 1]`.
+    fn support(&self) -> (f64, f64) {
+        (0., 1.)
+    }
+}
+
+impl DistributionND for Dirichlet {
+    fn get_dim(&self) -> usize {
+        self.alpha.len()
+    }
+}
+
+impl<'a> Continuous for &'a Dirichlet {
+    type PDFType = &'a [f64];
+    /// Calculates the probability density function for the given Dirichlet distribution at `x`.
+    ///
+    /// # Errors
+    /// Panics if `x` does not have the same dimension as the distribution, or if it does not lie
+    /// on the simplex (up to a tolerance of `1e-8` on its sum).
+    fn pdf(&self, x: Self::PDFType) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn ln_pdf(&self, x: Self::PDFType) -> f64 {
+        assert_eq!(x.len(), self.alpha.len(), "x has the wrong dimension");
+        assert!(
+            x.iter().all(|xi| (0. ..=1.).contains(xi)) && (x.iter().sum::<f64>() - 1.).abs() < 1e-8,
+            "x must lie on the simplex"
+        );
+        let ln_norm = lgamma(self.alpha.sum()) - self.alpha.iter().map(|&a| lgamma(a)).sum::<f64>();
+        ln_norm
+            + x.iter()
+                .zip(self.alpha.iter())
+                .map(|(xi, a)| (a - 1.) * xi.ln())
+                .sum::<f64>()
+    }
+}
+
+impl Mean for Dirichlet {
+    type MeanType = Vector;
+    /// Returns the mean of the Dirichlet distribution, which is `alpha / sum(alpha)`.
+    fn mean(&self) -> Vector {
+        let total = self.alpha.sum();
+        self.alpha.iter().map(|a| a / total).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::{mean, var};
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_sample() {
+        alea::set_seed(4);
+        let dist = Dirichlet::new([2., 2., 2., 2.]);
+        let samples = dist.sample_n(20_000);
+        assert_eq!(samples.shape(), [20_000, 4]);
+        for row in samples.rows() {
+            assert_approx_eq!(row.iter().sum::<f64>(), 1.);
+            assert!(row.iter().all(|&x| x > 0. && x < 1.));
+        }
+        for m in samples.sum_cols().iter() {
+            assert_approx_eq!(m / 20_000., 0.25, 2e-2);
+        }
+
+        let dist = Dirichlet::new([1., 3., 6.]);
+        let samples = dist.sample_n(20_000);
+        let means = samples.sum_cols();
+        for (m, expected) in means.iter().zip(dist.mean().iter()) {
+            assert_approx_eq!(m / 20_000., *expected, 2e-2);
+        }
+    }
+
+    #[test]
+    fn test_sample_small_alpha() {
+        alea::set_seed(12);
+        for alpha in &[vec![0.5, 0.5], vec![0.2, 0.2]] {
+            let dist = Dirichlet::new(alpha.clone());
+            let samples = dist.sample_n(20_000);
+            let total = alpha.iter().sum::<f64>();
+            for (i, a) in alpha.iter().enumerate() {
+                let x = samples.get_col_as_vector(i);
+                // the marginals are Beta(a, total - a)
+                let expected_var = a * (total - a) / (total.powi(2) * (total + 1.));
+                assert_approx_eq!(mean(&x), a / total, 2e-2);
+                assert_approx_eq!(var(&x), expected_var, 2e-2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pdf() {
+        // uniform on the simplex, with density 1 / (area of the simplex) = (k - 1)!
+        let dist = Dirichlet::new([1., 1., 1.]);
+        assert_approx_eq!((&dist).pdf(&[0.2, 0.3, 0.5]), 2.);
+
+        // with two components, this is a Beta distribution
+        let dist = Dirichlet::new([2., 5.]);
+        let beta = crate::distributions::Beta::new(2., 5.);
+        for &x in &[0.1, 0.35, 0.8] {
+            assert_approx_eq!((&dist).pdf(&[x, 1. - x]), beta.pdf(x));
+        }
+
+        assert!(Dirichlet::try_new([1.]).is_err());
+        assert!(Dirichlet::try_new([1., 0.]).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "simplex")]
+    fn test_pdf_off_simplex() {
+        let dist = Dirichlet::new([1., 1., 1.]);
+        (&dist).pdf(&[0.2, 0.3, 0.4]);
+    }
+}
@@ -2,6 +2,7 @@ use crate::distributions::*;
 
 /// Implements the [discrete uniform distribution](https://en.wikipedia.org/wiki/Discrete_uniform_distribution).
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DiscreteUniform {
     /// Lower bound for the discrete uniform distribution.
     lower: i64,
@@ -15,10 +16,18 @@ impl DiscreteUniform {
     /// # Errors
     /// Panics if `lower > upper`.
     pub fn new(lower: i64, upper: i64) -> Self {
+        Self::try_new(lower, upper).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new discrete uniform distribution with lower bound `lower` and upper bound `upper` (inclusive on both ends).
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if `lower > upper`.
+    pub fn try_new(lower: i64, upper: i64) -> Result<Self, DistError> {
         if lower > upper {
-            panic!("`Upper` must be larger than `lower`.");
+            return Err(DistError("`Upper` must be larger than `lower`."));
         }
-        DiscreteUniform { lower, upper }
+        Ok(DiscreteUniform { lower, upper })
     }
     pub fn set_lower(&mut self, lower: i64) -> &mut Self {
         if lower > self.upper {
@@ -34,6 +43,24 @@ impl DiscreteUniform {
         self.upper = upper;
         self
     }
+    /// Samples an integer from the given discrete uniform distribution. Both bounds are inclusive
+    /// and every integer in the range is equally likely.
+    pub fn sample_int(&self) -> i64 {
+        if self.lower == self.upper {
+            self.lower
+        } else {
+            alea::i64_in_range(self.lower, self.upper)
+        }
+    }
+    /// Generates a vector of `n` integers sampled from the given discrete uniform distribution.
+    pub fn sample_int_vec(&self, n: usize) -> Vec<i64> {
+        (0..n).map(|_| self.sample_int()).collect()
+    }
+    /// Returns the median, `(lower + upper) / 2`. If there are an even number of integers in the
+    /// support, this is halfway between the two middle values.
+    pub fn median(&self) -> f64 {
+        (self.lower + self.upper) as f64 / 2.
+    }
 }
 
 impl Default for DiscreteUniform {
@@ -46,7 +73,11 @@ impl Distribution for DiscreteUniform {
     type Output = f64;
     /// Samples from the given discrete uniform distribution.
     fn sample(&self) -> f64 {
-        alea::i64_in_range(self.lower, self.upper) as f64
+        self.sample_int() as f64
+    }
+    /// Returns the support of the distribution, which is the integers in `[lower, upper]`.
+    fn support(&self) -> (f64, f64) {
+        (self.lower as f64, self.upper as f64)
     }
 }
 
@@ -77,7 +108,7 @@ impl Mean for DiscreteUniform {
     type MeanType = f64;
     /// Calculates the mean, which for a Uniform(a, b) distribution is given by `(a + b) / 2`.
     fn mean(&self) -> f64 {
-        ((self.lower + self.upper) / 2) as f64
+        (self.lower + self.upper) as f64 / 2.
     }
 }
 
@@ -98,3 +129,44 @@ fn inrange() {
         assert!(x <= 6.);
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::{mean, var};
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_sample_int() {
+        let dist = DiscreteUniform::new(-3, 4);
+        let samples = dist.sample_int_vec(80000);
+        let mut counts = [0; 8];
+        for x in samples {
+            assert!((-3..=4).contains(&x));
+            counts[(x + 3) as usize] += 1;
+        }
+        // each value is expected 10000 times, with a standard deviation of about 94
+        for c in counts.iter() {
+            assert!((*c as i64 - 10000).abs() < 500, "{:?}", counts);
+        }
+
+        assert_eq!(DiscreteUniform::new(5, 5).sample_int_vec(10), vec![5; 10]);
+    }
+
+    #[test]
+    fn test_moments() {
+        let dist = DiscreteUniform::new(-3, 4);
+        assert_approx_eq!((-5..=6).map(|x| dist.pmf(x)).sum::<f64>(), 1.);
+        assert_eq!(dist.pmf(-4), 0.);
+        assert_eq!(dist.pmf(5), 0.);
+
+        assert_eq!(dist.mean(), 0.5);
+        assert_eq!(dist.median(), 0.5);
+        assert_eq!(dist.var(), 63. / 12.);
+
+        alea::set_seed(3);
+        let samples = dist.sample_n(1e6 as usize);
+        assert!((mean(&samples) - dist.mean()).abs() < 0.01);
+        assert!((var(&samples) - dist.var()).abs() < 0.05);
+    }
+}
@@ -1,8 +1,10 @@
 use crate::distributions::*;
+use crate::statistics::mean;
 
 /// Implements the [Exponential](https://en.wikipedia.org/wiki/Exponential_distribution)
 /// distribution.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Exponential {
     /// Rate parameter λ
     lambda: f64,
@@ -12,18 +14,43 @@ pub struct Exponential {
 }
 
 impl Exponential {
-    /// Create a new Exponential distribution with rate parameter `lambda`.
+    /// Create a new Exponential distribution with rate parameter `lambda`, so that the mean is
+    /// `1 / lambda`. This is the same as [from_rate](Exponential::from_rate).
     ///
     /// # Errors
     /// Panics if `lambda <= 0`.
     pub fn new(lambda: f64) -> Self {
+        Self::try_new(lambda).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new Exponential distribution with rate `rate`, with density `rate exp(-rate x)`.
+    ///
+    /// # Errors
+    /// Panics if `rate <= 0`.
+    pub fn from_rate(rate: f64) -> Self {
+        Self::new(rate)
+    }
+    /// Create a new Exponential distribution with mean (scale) `mean`, which is the same as a rate
+    /// of `1 / mean`.
+    ///
+    /// # Errors
+    /// Panics if `mean <= 0`.
+    pub fn from_mean(mean: f64) -> Self {
+        assert!(mean > 0., "Mean must be positive.");
+        Self::new(1. / mean)
+    }
+    /// Create a new Exponential distribution with rate parameter `lambda`.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if `lambda <= 0`.
+    pub fn try_new(lambda: f64) -> Result<Self, DistError> {
         if lambda <= 0. {
-            panic!("Lambda must be positive.");
+            return Err(DistError("Lambda must be positive."));
         }
-        Exponential {
+        Ok(Exponential {
             lambda,
             rng: Uniform::new(0., 1.),
-        }
+        })
     }
     pub fn set_lambda(&mut self, lambda: f64) -> &mut Self {
         if lambda <= 0. {
@@ -34,6 +61,20 @@ impl Exponential {
     }
 }
 
+impl Fit for Exponential {
+    const NPARAMS: usize = 1;
+    /// Fit an Exponential distribution to the data by maximum likelihood. The rate is the
+    /// reciprocal of the sample mean.
+    ///
+    /// # Errors
+    /// Panics if there is no data or if any data point is negative.
+    fn fit(data: &[f64]) -> Self {
+        assert!(!data.is_empty(), "Need at least one data point.");
+        assert!(data.iter().all(|&x| x >= 0.), "Data must be non-negative.");
+        Self::new(1. / mean(data))
+    }
+}
+
 impl Default for Exponential {
     fn default() -> Self {
         Self::new(1.)
@@ -50,6 +91,10 @@ impl Distribution for Exponential {
     fn sample(&self) -> f64 {
         -self.rng.sample().ln() / self.lambda
     }
+    /// Returns the support of the distribution, which is `[0, inf)`.
+    fn support(&self) -> (f64, f64) {
+        (0., f64::INFINITY)
+    }
 }
 
 impl Distribution1D for Exponential {
@@ -73,6 +118,10 @@ impl Continuous for Exponential {
         }
         self.lambda * (-self.lambda * x).exp()
     }
+    /// Calculates the `n`th raw moment, which is `n! / lambda^n`.
+    fn raw_moment(&self, n: u32) -> f64 {
+        (1..=n).map(|k| k as f64 / self.lambda).product()
+    }
 }
 
 impl Mean for Exponential {
@@ -97,6 +146,28 @@ mod tests {
     use crate::statistics::{mean, var};
     use approx_eq::assert_approx_eq;
 
+    #[test]
+    fn test_raw_moments() {
+        let dist = Exponential::new(2.5);
+        assert_eq!(dist.raw_moment(1), dist.mean());
+        assert_approx_eq!(dist.central_moment(2), dist.var(), 1e-12);
+        for n in 0..5 {
+            assert_approx_eq!(
+                dist.raw_moment(n),
+                dist.expected_value(|x| x.powi(n as i32)),
+                1e-8
+            );
+        }
+    }
+
+    #[test]
+    fn test_parameterizations() {
+        let dist = Exponential::from_mean(4.);
+        assert_eq!(dist.mean(), 4.);
+        assert_eq!(Exponential::from_rate(0.25).pdf(1.5), dist.pdf(1.5));
+        assert_eq!(Exponential::new(0.25).mean(), dist.mean());
+    }
+
     #[test]
     fn test_moments() {
         let data2 = Exponential::new(5.).sample_n(1e6 as usize);
@@ -0,0 +1,188 @@
+use crate::distributions::*;
+use crate::functions::{beta_inc, lgamma};
+
+/// Implements the [F](https://en.wikipedia.org/wiki/F-distribution) (Fisher-Snedecor)
+/// distribution, which is the distribution of the ratio of two independent chi square random
+/// variables, each divided by its degrees of freedom.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FisherSnedecor {
+    /// Degrees of freedom of the numerator
+    dof1: f64,
+    /// Degrees of freedom of the denominator
+    dof2: f64,
+    /// Gamma(dof1 / 2, 1) distribution used to sample the numerator.
+    num_gen: Gamma,
+    /// Gamma(dof2 / 2, 1) distribution used to sample the denominator.
+    den_gen: Gamma,
+}
+
+impl FisherSnedecor {
+    /// Create a new F distribution with `dof1` and `dof2` degrees of freedom.
+    ///
+    /// # Errors
+    /// Panics if either of the degrees of freedom is not positive.
+    pub fn new(dof1: f64, dof2: f64) -> Self {
+        Self::try_new(dof1, dof2).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new F distribution with `dof1` and `dof2` degrees of freedom.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if either of the degrees of freedom is not positive.
+    pub fn try_new(dof1: f64, dof2: f64) -> Result<Self, DistError> {
+        if dof1 <= 0. || dof2 <= 0. {
+            return Err(DistError("Degrees of freedom must be positive."));
+        }
+        Ok(FisherSnedecor {
+            dof1,
+            dof2,
+            num_gen: Gamma::new(dof1 / 2., 1.),
+            den_gen: Gamma::new(dof2 / 2., 1.),
+        })
+    }
+    pub fn set_dof1(&mut self, dof1: f64) -> &mut Self {
+        assert!(dof1 > 0., "Degrees of freedom must be positive.");
+        self.dof1 = dof1;
+        self.num_gen = Gamma::new(dof1 / 2., 1.);
+        self
+    }
+    pub fn set_dof2(&mut self, dof2: f64) -> &mut Self {
+        assert!(dof2 > 0., "Degrees of freedom must be positive.");
+        self.dof2 = dof2;
+        self.den_gen = Gamma::new(dof2 / 2., 1.);
+        self
+    }
+    /// Calculates the cumulative distribution function at `x`, using the [regularized incomplete
+    /// beta function](crate::functions::beta_inc).
+    pub fn cdf(&self, x: f64) -> f64 {
+        if x <= 0. {
+            return 0.;
+        }
+        if x == f64::INFINITY {
+            return 1.;
+        }
+        let (d1, d2) = (self.dof1, self.dof2);
+        beta_inc(d1 / 2., d2 / 2., d1 * x / (d1 * x + d2))
+    }
+}
+
+impl Default for FisherSnedecor {
+    fn default() -> Self {
+        Self::new(1., 1.)
+    }
+}
+
+impl Distribution for FisherSnedecor {
+    type Output = f64;
+    /// Samples from the given F distribution.
+    fn sample(&self) -> f64 {
+        let num = self.num_gen.sample() / self.dof1;
+        let den = self.den_gen.sample() / self.dof2;
+        num / den
+    }
+    /// Returns the support of the distribution, which is `[0, inf)`.
+    fn support(&self) -> (f64, f64) {
+        (0., f64::INFINITY)
+    }
+}
+
+impl Distribution1D for FisherSnedecor {
+    fn update(&mut self, params: &[f64]) {
+        self.set_dof1(params[0]).set_dof2(params[1]);
+    }
+}
+
+impl Continuous for FisherSnedecor {
+    type PDFType = f64;
+    /// Calculates the probability density function for the given F distribution at `x`.
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0. {
+            return 0.;
+        }
+        let (d1, d2) = (self.dof1, self.dof2);
+        let ln_beta = lgamma(d1 / 2.) + lgamma(d2 / 2.) - lgamma((d1 + d2) / 2.);
+        (0.5 * (d1 * (d1 * x).ln() + d2 * d2.ln() - (d1 + d2) * (d1 * x + d2).ln())
+            - x.ln()
+            - ln_beta)
+            .exp()
+    }
+}
+
+impl Mean for FisherSnedecor {
+    type MeanType = f64;
+    /// Calculates the mean of the F distribution, which is `dof2 / (dof2 - 2)` when `dof2` is
+    /// greater than 2, and infinity otherwise.
+    fn mean(&self) -> f64 {
+        if self.dof2 > 2. {
+            self.dof2 / (self.dof2 - 2.)
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+impl Variance for FisherSnedecor {
+    type VarianceType = f64;
+    /// Calculates the variance of the F distribution.
+    ///
+    /// # Remarks
+    /// This is infinity when `dof2` is in (2, 4], and not defined when `dof2` is less than or
+    /// equal to 2.
+    fn var(&self) -> f64 {
+        let (d1, d2) = (self.dof1, self.dof2);
+        if d2 > 4. {
+            2. * d2.powi(2) * (d1 + d2 - 2.) / (d1 * (d2 - 2.).powi(2) * (d2 - 4.))
+        } else if d2 > 2. {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::{mean, var};
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_moments() {
+        let dist = FisherSnedecor::new(5., 12.);
+        let data = dist.sample_n(1e6 as usize);
+        assert_approx_eq!(mean(&data), dist.mean(), 1e-2);
+        assert_approx_eq!(var(&data), dist.var(), 5e-2);
+    }
+
+    #[test]
+    fn test_sample_quantiles() {
+        alea::set_seed(9);
+        // dof1 = 1 needs Gamma(1/2) samples, and small dofs need Gamma shapes below 1/2
+        for &(dof1, dof2) in &[(1., 1.), (1., 7.), (0.5, 3.), (0.4, 0.6)] {
+            let dist = FisherSnedecor::new(dof1, dof2);
+            let mut data = dist.sample_n(20000);
+            data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for &p in &[0.1, 0.25, 0.5, 0.75, 0.9] {
+                let quantile = data[(p * 20000.) as usize];
+                assert!((dist.cdf(quantile) - p).abs() < 0.015);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cdf() {
+        // with dof1 = 2, the survival function is (1 + 2x / dof2)^(-dof2 / 2)
+        let dist = FisherSnedecor::new(2., 6.);
+        for &x in &[0.1, 1., 4., 12.] {
+            assert_approx_eq!(1. - dist.cdf(x), (1. + x / 3.).powi(-3), 1e-10);
+        }
+        assert_eq!(dist.cdf(0.), 0.);
+        assert_eq!(dist.cdf(f64::INFINITY), 1.);
+
+        let dist = FisherSnedecor::new(4.5, 9.);
+        for &x in &[0.2, 1., 2.5, 6.] {
+            assert!((dist.cdf(x) - dist.cdf_numerical(x)).abs() < 1e-8);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::distributions::*;
+use crate::statistics::{mean, sample_var};
+
+/// Fits the distribution family with the given name to the data, using the `fit` method of that
+/// distribution. The supported names are `"normal"`, `"gamma"`, `"exponential"`, and `"beta"`
+/// (case-insensitive).
+///
+/// # Errors
+/// Returns an error if the name is not one of the supported families. Panics if the data is not
+/// valid for the family (see the `fit` method of the corresponding distribution).
+pub fn fit_distribution(
+    name: &str,
+    data: &[f64],
+) -> Result<Box<dyn Continuous<PDFType = f64>>, &'static str> {
+    match name.to_lowercase().as_str() {
+        "normal" => Ok(Box::new(Normal::fit(data))),
+        "gamma" => Ok(Box::new(Gamma::fit(data))),
+        "exponential" => Ok(Box::new(Exponential::fit(data))),
+        "beta" => Ok(Box::new(Beta::fit(data))),
+        _ => Err("unknown distribution name"),
+    }
+}
+
+/// Calculates the [Akaike information criterion](https://en.wikipedia.org/wiki/Akaike_information_criterion)
+/// `-2 * ln(L) + 2 * k` of the distribution `D` after fitting it to the data, where `L` is the
+/// likelihood of the data under the fitted distribution and `k` is the number of fitted parameters.
+/// Lower values indicate a better fit.
+pub fn distribution_aic<D: Continuous<PDFType = f64> + Fit>(data: &[f64]) -> f64 {
+    let dist = D::fit(data);
+    let loglik = data.iter().map(|&x| dist.ln_pdf(x)).sum::<f64>();
+    -2. * loglik + 2. * D::NPARAMS as f64
+}
+
+/// Finds the distribution family, out of the named `candidates`, with the lowest AIC (see
+/// [distribution_aic](crate::distributions::distribution_aic)) for the data. The supported names
+/// are the same as for [fit_distribution](crate::distributions::fit_distribution). Families that
+/// cannot be fit to the data, such as those whose support does not contain all of the data, or
+/// those that would be degenerate because all of the data are equal, are never chosen.
+///
+/// # Errors
+/// Returns an error if any of the names is not one of the supported families, or if none of the
+/// candidates can be fit to the data.
+pub fn best_fit<'a>(data: &[f64], candidates: &[&'a str]) -> Result<&'a str, &'static str> {
+    let n = data.len();
+    let varying = data.iter().any(|&x| x != data[0]);
+    let positive = data.iter().all(|&x| x > 0.);
+    let nonnegative = data.iter().all(|&x| x >= 0.);
+    let unit = n > 1 && data.iter().all(|&x| x > 0. && x < 1.) && {
+        let (m, v) = (mean(data), sample_var(data));
+        v > 0. && v < m * (1. - m)
+    };
+
+    let mut best = None;
+    let mut best_aic = f64::INFINITY;
+    for &name in candidates {
+        let aic = match name.to_lowercase().as_str() {
+            "normal" if varying => distribution_aic::<Normal>(data),
+            "gamma" if n > 1 && positive && varying => distribution_aic::<Gamma>(data),
+            "exponential" if nonnegative && mean(data) > 0. => {
+                distribution_aic::<Exponential>(data)
+            }
+            "beta" if unit => distribution_aic::<Beta>(data),
+            "normal" | "gamma" | "exponential" | "beta" => f64::INFINITY,
+            _ => return Err("unknown distribution name"),
+        };
+        if aic < best_aic {
+            best = Some(name);
+            best_aic = aic;
+        }
+    }
+
+    best.ok_or("no candidate distribution can be fit to the data")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrate::quad5;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_fit_distribution() {
+        alea::set_seed(3);
+        let data = Normal::new(4., 1.5).sample_n(1000);
+        let dist = fit_distribution("normal", &data).unwrap();
+
+        // integrate over [-16, 16] in pieces, which covers the fitted distribution
+        let integrate = |f: &dyn Fn(f64) -> f64| {
+            (0..100)
+                .map(|i| quad5(f, -16. + 0.32 * i as f64, -16. + 0.32 * (i + 1) as f64))
+                .sum::<f64>()
+        };
+        assert_approx_eq!(integrate(&|x| dist.pdf(x)), 1., 1e-6);
+        assert_approx_eq!(integrate(&|x| x * dist.pdf(x)), mean(&data), 1e-6);
+
+        let positive = Gamma::new(2., 3.).sample_n(1000);
+        for name in ["gamma", "Exponential"] {
+            assert!(fit_distribution(name, &positive).is_ok());
+        }
+        assert!(fit_distribution("beta", &Beta::new(2., 3.).sample_n(1000)).is_ok());
+        assert!(fit_distribution("cauchy", &data).is_err());
+    }
+
+    #[test]
+    fn test_best_fit() {
+        alea::set_seed(5);
+        let candidates = ["normal", "exponential", "gamma"];
+
+        let data = Normal::new(5., 1.).sample_n(5000);
+        assert!(distribution_aic::<Normal>(&data) < distribution_aic::<Gamma>(&data));
+        assert!(distribution_aic::<Normal>(&data) < distribution_aic::<Exponential>(&data));
+        assert_eq!(best_fit(&data, &candidates), Ok("normal"));
+
+        // exponential and gamma cannot describe negative data
+        let data = Normal::new(0., 1.).sample_n(5000);
+        assert_eq!(best_fit(&data, &candidates), Ok("normal"));
+        assert!(best_fit(&data, &["gamma", "beta"]).is_err());
+
+        let data = Gamma::new(2., 0.5).sample_n(5000);
+        assert_eq!(best_fit(&data, &candidates), Ok("gamma"));
+
+        assert!(best_fit(&data, &["normal", "weibull"]).is_err());
+    }
+
+    #[test]
+    fn test_best_fit_constant() {
+        assert!(best_fit(&[2., 2., 2.], &["gamma", "normal"]).is_err());
+        assert_eq!(
+            best_fit(&[2., 2., 2.], &["gamma", "exponential"]),
+            Ok("exponential")
+        );
+        assert_eq!(
+            best_fit(&[2.], &["normal", "gamma", "exponential"]),
+            Ok("exponential")
+        );
+        assert!(best_fit(&[0., 0.], &["exponential"]).is_err());
+        assert!(best_fit(&[], &["normal", "gamma", "exponential", "beta"]).is_err());
+    }
+
+    #[test]
+    fn test_best_fit_zero() {
+        alea::set_seed(7);
+        let mut data = Exponential::new(2.).sample_n(1000);
+        data[0] = 0.;
+        assert_eq!(best_fit(&data, &["exponential"]), Ok("exponential"));
+        assert_eq!(
+            best_fit(&data, &["gamma", "exponential"]),
+            Ok("exponential")
+        );
+    }
+}
@@ -1,38 +1,78 @@
 use crate::distributions::*;
-use crate::functions::gamma;
+use crate::functions::{digamma, gamma, trigamma};
+use crate::statistics::mean;
 
 /// Implements the [Gamma](https://en.wikipedia.org/wiki/Gamma_distribution) distribution.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gamma {
     /// Shape parameter α.
     alpha: f64,
     /// Rate parameter β.
     beta: f64,
+    /// Constant `d = alpha - 1/3` used by the sampler (with `alpha + 1` in place of `alpha` if
+    /// `alpha < 1`).
+    d: f64,
+    /// Constant `c = 1 / sqrt(9d)` used by the sampler.
+    c: f64,
     normal_gen: Normal,
     uniform_gen: Uniform,
 }
 
 impl Gamma {
-    /// Create a new Gamma distribution with shape `alpha` and rate `beta`.
+    /// Create a new Gamma distribution with shape `alpha` and rate `beta`, so that the mean is
+    /// `alpha / beta`. This is the same as [from_shape_rate](Gamma::from_shape_rate).
     ///
     /// # Errors
     /// Panics if `alpha <= 0` or `beta <= 0`.
     pub fn new(alpha: f64, beta: f64) -> Self {
+        Self::try_new(alpha, beta).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new Gamma distribution with shape `shape` and rate `rate`, with density
+    /// proportional to `x^(shape - 1) exp(-rate x)` and mean `shape / rate`.
+    ///
+    /// # Errors
+    /// Panics if `shape <= 0` or `rate <= 0`.
+    pub fn from_shape_rate(shape: f64, rate: f64) -> Self {
+        Self::new(shape, rate)
+    }
+    /// Create a new Gamma distribution with shape `shape` and scale `scale`, with density
+    /// proportional to `x^(shape - 1) exp(-x / scale)` and mean `shape * scale`. This is the same
+    /// as a rate of `1 / scale`.
+    ///
+    /// # Errors
+    /// Panics if `shape <= 0` or `scale <= 0`.
+    pub fn from_shape_scale(shape: f64, scale: f64) -> Self {
+        assert!(scale > 0., "Scale must be positive.");
+        Self::new(shape, 1. / scale)
+    }
+    /// Create a new Gamma distribution with shape `alpha` and rate `beta`.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if `alpha <= 0` or `beta <= 0`.
+    pub fn try_new(alpha: f64, beta: f64) -> Result<Self, DistError> {
         if alpha <= 0. || beta <= 0. {
-            panic!("Both alpha and beta must be positive.");
+            return Err(DistError("Both alpha and beta must be positive."));
         }
-        Gamma {
+        let (d, c) = sampler_constants(alpha);
+        Ok(Gamma {
             alpha,
             beta,
+            d,
+            c,
             normal_gen: Normal::new(0., 1.),
             uniform_gen: Uniform::new(0., 1.),
-        }
+        })
     }
     pub fn set_alpha(&mut self, alpha: f64) -> &mut Self {
         if alpha <= 0. {
             panic!("Alpha must be positive.");
         }
         self.alpha = alpha;
+        let (d, c) = sampler_constants(alpha);
+        self.d = d;
+        self.c = c;
         self
     }
     pub fn set_beta(&mut self, beta: f64) -> &mut Self {
@@ -42,6 +82,64 @@ impl Gamma {
         self.beta = beta;
         self
     }
+    /// Samples with the Marsaglia and Tsang method, using the constants `d` and `c`.
+    fn sample_marsaglia_tsang(&self) -> f64 {
+        let d = self.d;
+        loop {
+            let (x, v) = loop {
+                let x = self.normal_gen.sample();
+                let v = (1. + self.c * x).powi(3);
+                if v > 0. {
+                    break (x, v);
+                }
+            };
+            let u = self.uniform_gen.sample();
+            if u < 1. - 0.0331 * x.powi(4) {
+                return d * v / self.beta;
+            }
+            if u.ln() < 0.5 * x.powi(2) + d * (1. - v + v.ln()) {
+                return d * v / self.beta;
+            }
+        }
+    }
+}
+
+/// Computes the constants `d` and `c` for the Marsaglia and Tsang sampler. The sampler needs a
+/// shape of at least 1, so smaller shapes are boosted by 1 (see [Gamma::sample]).
+fn sampler_constants(alpha: f64) -> (f64, f64) {
+    let d = if alpha < 1. { alpha + 1. } else { alpha } - 1. / 3.;
+    (d, 1. / (9. * d).sqrt())
+}
+
+impl Fit for Gamma {
+    const NPARAMS: usize = 2;
+    /// Fit a Gamma distribution to the data by maximum likelihood. The shape is found using Newton's
+    /// method on the score equation `ln(alpha) - digamma(alpha) = ln(mean(x)) - mean(ln(x))`, and the
+    /// rate is then given by `alpha / mean(x)`.
+    ///
+    /// # Errors
+    /// Panics if there are fewer than two data points, if any data point is not positive, or if all
+    /// of the data points are equal.
+    fn fit(data: &[f64]) -> Self {
+        assert!(data.len() > 1, "Need at least two data points.");
+        assert!(data.iter().all(|&x| x > 0.), "Data must be positive.");
+
+        let data_mean = mean(data);
+        let s = data_mean.ln() - data.iter().map(|x| x.ln()).sum::<f64>() / data.len() as f64;
+        assert!(s > 0., "Data must not all be equal.");
+
+        // approximate solution from Minka 2002 as a starting point
+        let mut alpha = (3. - s + ((s - 3.).powi(2) + 24. * s).sqrt()) / (12. * s);
+        for _ in 0..100 {
+            let step = (alpha.ln() - digamma(alpha) - s) / (1. / alpha - trigamma(alpha));
+            alpha = (alpha - step).max(alpha / 10.);
+            if (step / alpha).abs() < 1e-12 {
+                break;
+            }
+        }
+
+        Self::new(alpha, alpha / data_mean)
+    }
 }
 
 impl Default for Gamma {
@@ -56,25 +154,18 @@ impl Distribution for Gamma {
     ///
     /// # Remarks
     /// Uses the algorithm from Marsaglia and Tsang 2000. Applies the squeeze
-    /// method and has nearly constant average time for `alpha >= 1`.
+    /// method and has nearly constant average time for `alpha >= 1`. For `alpha < 1`, a sample
+    /// from Gamma(alpha + 1) is multiplied by `U^(1 / alpha)`, where `U` is uniform on `(0, 1]`.
     fn sample(&self) -> f64 {
-        let d = self.alpha - 1. / 3.;
-        loop {
-            let (x, v) = loop {
-                let x = self.normal_gen.sample();
-                let v = (1. + x / (9. * d).sqrt()).powi(3);
-                if v > 0. {
-                    break (x, v);
-                }
-            };
-            let u = self.uniform_gen.sample();
-            if u < 1. - 0.0331 * x.powi(4) {
-                return d * v / self.beta;
-            }
-            if u.ln() < 0.5 * x.powi(2) + d * (1. - v + v.ln()) {
-                return d * v / self.beta;
-            }
+        if self.alpha < 1. {
+            let u = 1. - self.uniform_gen.sample();
+            return self.sample_marsaglia_tsang() * u.powf(1. / self.alpha);
         }
+        self.sample_marsaglia_tsang()
+    }
+    /// Returns the support of the distribution, which is `(0, inf)`.
+    fn support(&self) -> (f64, f64) {
+        (0., f64::INFINITY)
     }
 }
 
@@ -98,6 +189,12 @@ impl Continuous for Gamma {
             * x.powf(self.alpha - 1.)
             * (-self.beta * x).exp()
     }
+    /// Calculates the `n`th raw moment, which is `alpha (alpha + 1) ... (alpha + n - 1) / beta^n`.
+    fn raw_moment(&self, n: u32) -> f64 {
+        (0..n)
+            .map(|k| (self.alpha + k as f64) / self.beta)
+            .product()
+    }
 }
 
 impl Mean for Gamma {
@@ -122,10 +219,85 @@ mod tests {
     use crate::statistics::{mean, var};
     use approx_eq::assert_approx_eq;
 
+    #[test]
+    fn test_raw_moments() {
+        let dist = Gamma::new(2.5, 4.);
+        assert_eq!(dist.raw_moment(1), dist.mean());
+        assert_approx_eq!(dist.central_moment(2), dist.var(), 1e-12);
+        for n in 0..5 {
+            assert_approx_eq!(
+                dist.raw_moment(n),
+                dist.expected_value(|x| x.powi(n as i32)),
+                1e-8
+            );
+        }
+    }
+
+    #[test]
+    fn test_parameterizations() {
+        let rate = Gamma::from_shape_rate(2.5, 4.);
+        let scale = Gamma::from_shape_scale(2.5, 0.25);
+        assert_eq!(rate.mean(), 2.5 / 4.);
+        assert_eq!(rate.mean(), scale.mean());
+        assert_eq!(rate.var(), scale.var());
+        for &x in &[0.1, 0.5, 1., 3.] {
+            assert_eq!(rate.pdf(x), scale.pdf(x));
+            assert_eq!(Gamma::new(2.5, 4.).pdf(x), rate.pdf(x));
+        }
+
+        alea::set_seed(3);
+        let a = rate.sample_n(100);
+        alea::set_seed(3);
+        let b = scale.sample_n(100);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = "Scale must be positive.")]
+    fn test_from_shape_scale_zero() {
+        Gamma::from_shape_scale(1., 0.);
+    }
+
     #[test]
     fn test_moments() {
         let data = Gamma::new(2., 4.).sample_n(1e6 as usize);
         assert_approx_eq!(0.5, mean(&data), 1e-2);
         assert_approx_eq!(0.125, var(&data), 1e-2);
     }
+
+    #[test]
+    fn test_small_shape() {
+        alea::set_seed(6);
+        for &alpha in &[0.1, 0.3, 0.8] {
+            let dist = Gamma::new(alpha, 2.);
+            let data = dist.sample_n(1e6 as usize);
+            assert!(data.iter().all(|&x| x >= 0.));
+            assert_approx_eq!(dist.mean(), mean(&data), 1e-2);
+            assert_approx_eq!(dist.var(), var(&data), 3e-2);
+        }
+    }
+
+    #[test]
+    fn test_set_alpha() {
+        alea::set_seed(5);
+        let mut dist = Gamma::new(1., 3.);
+        dist.set_alpha(6.);
+        let data = dist.sample_n(1e6 as usize);
+        assert_approx_eq!(2., mean(&data), 1e-2);
+        assert_approx_eq!(6. / 9., var(&data), 1e-2);
+    }
+
+    #[test]
+    fn test_fit() {
+        alea::set_seed(7);
+        let data = Gamma::new(3., 2.).sample_n(1e5 as usize);
+        let fitted = Gamma::fit(&data);
+        assert_approx_eq!(fitted.alpha, 3., 3e-2);
+        assert_approx_eq!(fitted.beta, 2., 3e-2);
+
+        let data = Gamma::new(1.5, 10.).sample_n(1e5 as usize);
+        let fitted = Gamma::fit(&data);
+        assert_approx_eq!(fitted.alpha, 1.5, 3e-2);
+        assert_approx_eq!(fitted.beta, 10., 3e-2);
+    }
 }
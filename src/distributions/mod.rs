@@ -5,8 +5,11 @@ mod bernoulli;
 mod beta;
 mod binomial;
 mod chi_squared;
+mod dirichlet;
 mod discreteuniform;
 mod exponential;
+mod fisher_snedecor;
+mod fit;
 mod gamma;
 mod multivariatenormal;
 mod normal;
@@ -14,14 +17,33 @@ mod pareto;
 mod poisson;
 mod t;
 mod uniform;
+mod weighted;
 
+use crate::functions::binom_coeff;
+use crate::integrate::quad_adaptive;
 use crate::linalg::{Matrix, Vector};
 
+/// An error returned when a distribution is created with invalid parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DistError(&'static str);
+
+impl std::fmt::Display for DistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for DistError {}
+
 /// The primary trait defining a probability distribution.
 pub trait Distribution: Send + Sync {
     type Output;
     /// Samples from the given probability distribution.
     fn sample(&self) -> Self::Output;
+    /// Returns the lower and upper bounds of the support of the distribution. Unbounded sides are
+    /// given as `-inf` or `inf`. For multidimensional distributions, these are the bounds of each
+    /// component.
+    fn support(&self) -> (f64, f64);
 }
 
 /// A trait defining a one dimensional distribution.
@@ -30,6 +52,34 @@ pub trait Distribution1D: Distribution<Output = f64> {
     fn sample_n(&self, n: usize) -> Vector {
         (0..n).map(|_| self.sample()).collect()
     }
+    /// Generates a vector of `n` randomly sampled values from the given probability distribution,
+    /// sampling in parallel.
+    ///
+    /// # Remarks
+    /// The samples are drawn in fixed-size chunks, each with its own random number generator
+    /// seeded from a base seed taken from the calling thread's generator. Setting the seed with
+    /// `alea::set_seed` before calling this therefore gives reproducible results regardless of
+    /// how the chunks are scheduled across threads.
+    #[cfg(feature = "rayon")]
+    fn sample_n_par(&self, n: usize) -> Vector {
+        use rayon::prelude::*;
+
+        const CHUNK_SIZE: usize = 8192;
+        let base_seed = alea::u64();
+        let nchunks = n.div_ceil(CHUNK_SIZE);
+
+        (0..nchunks)
+            .into_par_iter()
+            .flat_map_iter(|chunk| {
+                alea::set_seed(
+                    base_seed.wrapping_add((chunk as u64).wrapping_mul(0x9E3779B97F4A7C15)),
+                );
+                let len = CHUNK_SIZE.min(n - chunk * CHUNK_SIZE);
+                (0..len).map(|_| self.sample()).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
     /// Generates a matrix of size `n x m` with values randomly sampled from the given
     /// distribution.
     fn sample_matrix(&self, nrows: usize, ncols: usize) -> Matrix {
@@ -68,6 +118,13 @@ pub trait Variance {
     fn var(&self) -> Self::VarianceType;
 }
 
+/// Provides a trait for computing the (Shannon) entropy of a distribution, in nats, where there is
+/// a closed-form expression.
+pub trait Entropy {
+    /// Calculates the entropy of the distribution.
+    fn entropy(&self) -> f64;
+}
+
 /// Provides a trait for interacting with continuous probability distributions.
 pub trait Continuous {
     type PDFType;
@@ -77,6 +134,65 @@ pub trait Continuous {
     fn ln_pdf(&self, x: Self::PDFType) -> f64 {
         self.pdf(x).ln()
     }
+    /// Calculates the [cumulative distribution
+    /// function](https://en.wikipedia.org/wiki/Cumulative_distribution_function) at `x` by
+    /// numerically integrating the pdf from the lower bound of the support, using
+    /// [quad_adaptive](crate::integrate::quad_adaptive). This works for any one dimensional
+    /// distribution, but a closed form should be preferred where one is available.
+    fn cdf_numerical(&self, x: f64) -> f64
+    where
+        Self: Continuous<PDFType = f64> + Distribution + Sized,
+    {
+        let (lower, upper) = self.support();
+        if x <= lower {
+            0.
+        } else if x >= upper {
+            1.
+        } else {
+            quad_adaptive(|t| self.pdf(t), lower, x, 1e-10).clamp(0., 1.)
+        }
+    }
+    /// Calculates the [expected value](https://en.wikipedia.org/wiki/Expected_value) `E[g(X)]`
+    /// of some function `g` of the random variable by numerically integrating `g(x) * pdf(x)`
+    /// over the support, using [quad_adaptive](crate::integrate::quad_adaptive).
+    fn expected_value<F: Fn(f64) -> f64>(&self, g: F) -> f64
+    where
+        Self: Continuous<PDFType = f64> + Distribution + Sized,
+    {
+        let (lower, upper) = self.support();
+        quad_adaptive(|x| g(x) * self.pdf(x), lower, upper, 1e-10)
+    }
+    /// Calculates the `n`th [raw moment](https://en.wikipedia.org/wiki/Moment_(mathematics))
+    /// `E[X^n]` of the distribution with [expected_value](Continuous::expected_value).
+    /// Distributions with closed-form moments override this.
+    fn raw_moment(&self, n: u32) -> f64
+    where
+        Self: Continuous<PDFType = f64> + Distribution + Sized,
+    {
+        self.expected_value(|x| x.powi(n as i32))
+    }
+    /// Calculates the `n`th central moment `E[(X - E[X])^n]` of the distribution, by expanding it
+    /// in terms of the [raw moments](Continuous::raw_moment).
+    fn central_moment(&self, n: u32) -> f64
+    where
+        Self: Continuous<PDFType = f64> + Distribution + Sized,
+    {
+        let mu = self.raw_moment(1);
+        (0..=n)
+            .map(|k| {
+                let raw = if k == 0 { 1. } else { self.raw_moment(k) };
+                binom_coeff(n as u64, k as u64) as f64 * raw * (-mu).powi((n - k) as i32)
+            })
+            .sum()
+    }
+}
+
+/// Provides a trait for fitting a probability distribution to data.
+pub trait Fit: Sized {
+    /// The number of parameters estimated when fitting the distribution.
+    const NPARAMS: usize;
+    /// Fits the distribution to the data.
+    fn fit(data: &[f64]) -> Self;
 }
 
 /// Provides a trait for interacting with discrete probability distributions.
@@ -89,8 +205,11 @@ pub use self::bernoulli::Bernoulli;
 pub use self::beta::Beta;
 pub use self::binomial::Binomial;
 pub use self::chi_squared::ChiSquared;
+pub use self::dirichlet::Dirichlet;
 pub use self::discreteuniform::DiscreteUniform;
 pub use self::exponential::Exponential;
+pub use self::fisher_snedecor::FisherSnedecor;
+pub use self::fit::*;
 pub use self::gamma::Gamma;
 pub use self::multivariatenormal::*;
 pub use self::normal::Normal;
@@ -98,3 +217,82 @@ pub use self::pareto::Pareto;
 pub use self::poisson::Poisson;
 pub use self::t::*;
 pub use self::uniform::Uniform;
+pub use self::weighted::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_in_support<D: Distribution1D>(dist: D) {
+        let (lower, upper) = dist.support();
+        for x in dist.sample_n(10000) {
+            assert!(lower <= x && x <= upper, "{} not in [{}, {}]", x, lower, upper);
+        }
+    }
+
+    #[test]
+    fn test_try_new() {
+        let err = Normal::try_new(0., -1.).unwrap_err();
+        assert_eq!(err.to_string(), "Sigma must be non-negative.");
+        assert!(Normal::try_new(0., 1.).is_ok());
+
+        assert!(Gamma::try_new(0., 1.).is_err());
+        assert!(Gamma::try_new(2., 1.).is_ok());
+        assert!(Beta::try_new(1., -1.).is_err());
+        assert!(Uniform::try_new(1., 0.).is_err());
+        assert!(Exponential::try_new(0.).is_err());
+        assert!(Poisson::try_new(-1.).is_err());
+        assert!(Binomial::try_new(10, 1.5).is_err());
+        assert!(Bernoulli::try_new(-0.1).is_err());
+        assert!(DiscreteUniform::try_new(3, 2).is_err());
+        assert!(ChiSquared::try_new(0).is_err());
+        assert!(ChiSquared::try_new(2).is_ok());
+        assert!(T::try_new(0.).is_err());
+        assert!(FisherSnedecor::try_new(1., 0.).is_err());
+        assert!(Pareto::try_new(1., 0.).is_err());
+        assert!(MVN::try_new(vec![0., 0.], Matrix::new(vec![1., 0.5, 0.2, 1.], 2, 2)).is_err());
+        assert!(MVN::try_new(vec![0.], Matrix::new(vec![1., 0., 0., 1.], 2, 2)).is_err());
+        assert!(MVN::try_new(vec![0., 0.], Matrix::new(vec![1., 0.5, 0.5, 1.], 2, 2)).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "Sigma must be non-negative.")]
+    fn test_new_panics() {
+        Normal::new(0., -1.);
+    }
+
+    #[test]
+    fn test_numerical_moments() {
+        let beta = Beta::new(2., 5.);
+        assert!((beta.raw_moment(1) - beta.mean()).abs() < 1e-8);
+        assert!((beta.central_moment(2) - beta.var()).abs() < 1e-8);
+        let chi2 = ChiSquared::new(4);
+        assert!((chi2.raw_moment(1) - chi2.mean()).abs() < 1e-6);
+        assert!((chi2.central_moment(2) - chi2.var()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_support() {
+        assert_in_support(Normal::new(2., 3.));
+        assert_in_support(Gamma::new(2., 3.));
+        assert_in_support(Beta::new(0.5, 0.5));
+        assert_in_support(Uniform::new(-3., 7.));
+        assert_in_support(Exponential::new(4.));
+        assert_in_support(Poisson::new(3.));
+        assert_in_support(Binomial::new(10, 0.5));
+        assert_in_support(Bernoulli::new(0.3));
+        assert_in_support(DiscreteUniform::new(-4, 6));
+        assert_in_support(ChiSquared::new(3));
+        assert_in_support(T::new(4.));
+        assert_in_support(FisherSnedecor::new(3., 5.));
+        assert_in_support(Pareto::new(3., 2.));
+
+        assert_eq!(Exponential::new(1.).support(), (0., f64::INFINITY));
+        assert_eq!(Uniform::new(-3., 7.).support(), (-3., 7.));
+        assert_eq!(Beta::new(2., 3.).support(), (0., 1.));
+        assert_eq!(
+            Normal::default().support(),
+            (f64::NEG_INFINITY, f64::INFINITY)
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use std::f64::consts::PI;
 
-use super::{Continuous, Distribution, Distribution1D, DistributionND, Mean, Normal, Variance};
+use super::{
+    Continuous, DistError, Distribution, Distribution1D, DistributionND, Mean, Normal, Variance,
+};
 use crate::prelude::{Dot, Matrix, Vector};
 
 /// [Multivariate normal distribution](https://en.wikipedia.org/wiki/Multivariate_normal_distribution).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MVN {
     mean: Vector,
     covariance_matrix: Matrix,
@@ -16,7 +19,27 @@ pub struct MVN {
 pub type MultivariateNormal = MVN;
 
 impl MVN {
+    /// Create a new multivariate normal distribution with mean vector `mean` and covariance matrix
+    /// `covariance_matrix`.
+    ///
+    /// # Errors
+    /// Panics if the covariance matrix is not symmetric or if its dimensions do not match those of
+    /// the mean vector.
     pub fn new<V, M>(mean: V, covariance_matrix: M) -> Self
+    where
+        V: Into<Vector>,
+        M: Into<Matrix>,
+    {
+        Self::try_new(mean, covariance_matrix).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create a new multivariate normal distribution with mean vector `mean` and covariance matrix
+    /// `covariance_matrix`. Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if the covariance matrix is not symmetric or if its dimensions do not match
+    /// those of the mean vector.
+    pub fn try_new<V, M>(mean: V, covariance_matrix: M) -> Result<Self, DistError>
     where
         V: Into<Vector>,
         M: Into<Matrix>,
@@ -24,12 +47,14 @@ impl MVN {
         let m = mean.into();
         let c = covariance_matrix.into();
 
-        assert!(c.is_symmetric(), "covariance matrix must be symmetric");
-        assert_eq!(
-            m.len(),
-            c.ncols,
-            "mean vector and covariance matrix must have the same dimensions"
-        );
+        if !c.is_symmetric() {
+            return Err(DistError("covariance matrix must be symmetric"));
+        }
+        if m.len() != c.ncols {
+            return Err(DistError(
+                "mean vector and covariance matrix must have the same dimensions",
+            ));
+        }
 
         // don't really want to compute these if not necessary but if you make these option<..> and
         // compute only when necessary it gets kind of nasty because you need &mut self for e.g.
@@ -39,13 +64,13 @@ impl MVN {
         let cinv = (&c).inv();
         let cdet = (&c).det();
 
-        Self {
+        Ok(Self {
             mean: m,
             covariance_matrix: c,
             inverse_covariance_matrix: cinv,
             covariance_determinant: cdet,
             decomposed_covariance_matrix: l,
-        }
+        })
     }
 }
 
@@ -55,6 +80,10 @@ impl Distribution for MVN {
         let z = Normal::default().sample_n(self.mean.len());
         &self.mean + self.decomposed_covariance_matrix.dot(z)
     }
+    /// Returns the support of the distribution, which is the whole real line in each dimension.
+    fn support(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
 }
 
 impl DistributionND for MVN {
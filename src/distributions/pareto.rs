@@ -2,6 +2,7 @@ use crate::distributions::*;
 
 /// Implements the [Pareto](https://en.wikipedia.org/wiki/Pareto_distribution) distribution.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pareto {
     /// Shape parameter α.
     alpha: f64,
@@ -15,10 +16,18 @@ impl Pareto {
     /// # Errors
     /// Panics if `alpha <= 0` or `minval <= 0`.
     pub fn new(alpha: f64, minval: f64) -> Self {
+        Self::try_new(alpha, minval).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new Pareto distribution with shape `alpha` and minimum value `minval`.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if `alpha <= 0` or `minval <= 0`.
+    pub fn try_new(alpha: f64, minval: f64) -> Result<Self, DistError> {
         if alpha <= 0. || minval <= 0. {
-            panic!("Both alpha and beta must be positive.");
+            return Err(DistError("Both alpha and minval must be positive."));
         }
-        Pareto { alpha, minval }
+        Ok(Pareto { alpha, minval })
     }
     pub fn set_alpha(&mut self, alpha: f64) -> &mut Self {
         if alpha <= 0. {
@@ -49,6 +58,10 @@ impl Distribution for Pareto {
         let u = alea::f64();
         self.minval / u.powf(1. / self.alpha)
     }
+    /// Returns the support of the distribution, which is `[minval, inf)`.
+    fn support(&self) -> (f64, f64) {
+        (self.minval, f64::INFINITY)
+    }
 }
 
 impl Distribution1D for Pareto {
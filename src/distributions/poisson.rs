@@ -1,9 +1,10 @@
 use crate::distributions::*;
-use crate::functions::gamma;
+use crate::functions::ln_factorial;
 
 /// Implements the [Poisson](https://en.wikipedia.org/wiki/https://en.wikipedia.org/wiki/Poisson_distribution)
 /// distribution.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Poisson {
     /// Rate parameter for the Poisson distribution.
     lambda: f64,
@@ -15,10 +16,18 @@ impl Poisson {
     /// # Errors
     /// Panics if `lambda <= 0.0`.
     pub fn new(lambda: f64) -> Self {
+        Self::try_new(lambda).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new Poisson distribution with rate parameter `lambda`.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if `lambda <= 0.0`.
+    pub fn try_new(lambda: f64) -> Result<Self, DistError> {
         if lambda <= 0. {
-            panic!("`Lambda` must be positive.");
+            return Err(DistError("`Lambda` must be positive."));
         }
-        Poisson { lambda }
+        Ok(Poisson { lambda })
     }
     pub fn set_lambda(&mut self, lambda: f64) -> &mut Self {
         if lambda <= 0. {
@@ -46,6 +55,10 @@ impl Distribution for Poisson {
             sample_ptrs(self.lambda)
         }
     }
+    /// Returns the support of the distribution, which is the non-negative integers.
+    fn support(&self) -> (f64, f64) {
+        (0., f64::INFINITY)
+    }
 }
 
 impl Distribution1D for Poisson {
@@ -57,13 +70,13 @@ impl Distribution1D for Poisson {
 impl Discrete for Poisson {
     /// Calculates the [probability mass
     /// function](https://en.wikipedia.org/wiki/Probability_mass_function) for the given Poisson
-    /// distribution at `k`.
-    ///
+    /// distribution at `k`. The computation is done in log space, so it does not overflow for
+    /// large `k`.
     fn pmf(&self, k: i64) -> f64 {
         if k < 0 {
             0.
         } else {
-            self.lambda.powi(k as i32) * (-self.lambda).exp() / gamma(k as f64)
+            (k as f64 * self.lambda.ln() - self.lambda - ln_factorial(k as u64)).exp()
         }
     }
 }
@@ -116,7 +129,7 @@ fn sample_ptrs(lam: f64) -> f64 {
             continue;
         }
         if (V.ln() + invalpha.ln() - (a / (us * us) + b).ln())
-            <= (-lam + k * loglam - gamma(k + 1.).ln())
+            <= (-lam + k * loglam - ln_factorial(k as u64))
         {
             return k;
         }
@@ -143,4 +156,18 @@ mod tests {
         assert_approx_eq!(mean42, 42., 1e-2);
         assert_approx_eq!(var42, 42., 1e-2);
     }
+
+    #[test]
+    fn test_pmf() {
+        let p = Poisson::new(2.5);
+        assert_eq!(p.pmf(-1), 0.);
+        assert_approx_eq!(p.pmf(0), (-2.5_f64).exp());
+        assert_approx_eq!(p.pmf(3), 0.2137630172497364);
+        assert_approx_eq!((0..50).map(|k| p.pmf(k)).sum::<f64>(), 1.);
+
+        // 1000! overflows, but the pmf is still finite
+        let p = Poisson::new(1000.);
+        assert!(p.pmf(1000).is_finite());
+        assert_approx_eq!(p.pmf(1000), 0.0126146113487215);
+    }
 }
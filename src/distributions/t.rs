@@ -1,8 +1,9 @@
 use crate::distributions::*;
-use crate::functions::gamma;
+use crate::functions::{beta_inc, gamma};
 
 /// Implements the [Student's T](https://en.wikipedia.org/wiki/Student%27s_t-distribution) distribution.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct T {
     /// Degrees of freedom
     dof: f64,
@@ -11,19 +12,39 @@ pub struct T {
 pub type StudentsT = T;
 
 impl T {
-    /// Create a new t distribution with
+    /// Create a new t distribution with `dof` degrees of freedom.
     ///
     /// # Errors
     /// Panics if degrees of freedom is not positive.
     pub fn new(dof: f64) -> Self {
-        assert!(dof > 0., "Degrees of freedom must be positive.");
-        T { dof }
+        Self::try_new(dof).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new t distribution with `dof` degrees of freedom.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if degrees of freedom is not positive.
+    pub fn try_new(dof: f64) -> Result<Self, DistError> {
+        if dof <= 0. {
+            return Err(DistError("Degrees of freedom must be positive."));
+        }
+        Ok(T { dof })
     }
     pub fn set_dof(&mut self, dof: f64) -> &mut Self {
         assert!(dof > 0., "Degrees of freedom must be positive.");
         self.dof = dof;
         self
     }
+    /// Calculates the cumulative distribution function at `x`, using the [regularized incomplete
+    /// beta function](crate::functions::beta_inc).
+    pub fn cdf(&self, x: f64) -> f64 {
+        let tail = 0.5 * beta_inc(self.dof / 2., 0.5, self.dof / (self.dof + x * x));
+        if x > 0. {
+            1. - tail
+        } else {
+            tail
+        }
+    }
 }
 
 impl Default for T {
@@ -39,6 +60,10 @@ impl Distribution for T {
         (self.dof / 2.).sqrt() * Normal::default().sample()
             / Gamma::new(self.dof / 2., 1.).sample().sqrt()
     }
+    /// Returns the support of the distribution, which is the whole real line.
+    fn support(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
 }
 
 impl Distribution1D for T {
@@ -102,4 +127,15 @@ mod tests {
         let data = t.sample_n(1e6 as usize);
         assert_approx_eq!(mean(&data), 0., 1e-2);
     }
+
+    #[test]
+    fn test_cdf() {
+        // closed forms for one (Cauchy) and two degrees of freedom
+        let (t1, t2) = (T::new(1.), T::new(2.));
+        for &x in &[-5., -0.7, 0.3, 2., 40.] {
+            assert_approx_eq!(t1.cdf(x), 0.5 + f64::atan(x) / std::f64::consts::PI, 1e-10);
+            assert_approx_eq!(t2.cdf(x), 0.5 + x / (2. * (2. + x * x).sqrt()), 1e-10);
+        }
+        assert_eq!(t1.cdf(0.), 0.5);
+    }
 }
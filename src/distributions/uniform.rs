@@ -3,6 +3,7 @@ use crate::distributions::*;
 /// Implements the [Uniform](https://en.wikipedia.org/wiki/Uniform_distribution_(continuous))
 /// distribution.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Uniform {
     /// Lower bound for the Uniform distribution.
     lower: f64,
@@ -16,10 +17,18 @@ impl Uniform {
     /// # Errors
     /// Panics if `lower > upper`.
     pub fn new(lower: f64, upper: f64) -> Self {
+        Self::try_new(lower, upper).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Create a new Uniform distribution with lower bound `lower` and upper bound `upper`.
+    /// Returns an error instead of panicking if the parameters are invalid.
+    ///
+    /// # Errors
+    /// Returns an error if `lower > upper`.
+    pub fn try_new(lower: f64, upper: f64) -> Result<Self, DistError> {
         if lower > upper {
-            panic!("`Upper` must be larger than `lower`.");
+            return Err(DistError("`Upper` must be larger than `lower`."));
         }
-        Uniform { lower, upper }
+        Ok(Uniform { lower, upper })
     }
     pub fn set_lower(&mut self, lower: f64) -> &mut Self {
         if lower > self.upper {
@@ -49,6 +58,10 @@ impl Distribution for Uniform {
     fn sample(&self) -> f64 {
         (self.upper - self.lower) * alea::f64() + self.lower
     }
+    /// Returns the support of the distribution, which is `[lower, upper]`.
+    fn support(&self) -> (f64, f64) {
+        (self.lower, self.upper)
+    }
 }
 
 impl Distribution1D for Uniform {
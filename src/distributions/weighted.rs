@@ -0,0 +1,86 @@
+/// Computes the cumulative sums of the weights, checking that they are valid.
+pub(crate) fn cumulative_weights(weights: &[f64]) -> Vec<f64> {
+    assert!(
+        weights.iter().all(|&w| w >= 0.),
+        "weights must be non-negative"
+    );
+    let cumulative = weights
+        .iter()
+        .scan(0., |acc, w| {
+            *acc += w;
+            Some(*acc)
+        })
+        .collect::<Vec<_>>();
+    assert!(
+        cumulative.last().is_some_and(|&total| total > 0.),
+        "weights must have a positive sum"
+    );
+    cumulative
+}
+
+/// Draws one index with probability proportional to its weight, using the cumulative weights
+/// (see [cumulative_weights]) by binary search.
+pub(crate) fn draw_index(cumulative: &[f64]) -> usize {
+    let u = alea::f64() * cumulative[cumulative.len() - 1];
+    cumulative.partition_point(|&c| c <= u)
+}
+
+/// Samples a single value from `values`, where each value is chosen with probability proportional
+/// to its weight, using the random seed `seed`.
+///
+/// # Errors
+/// Panics if `values` and `weights` have different lengths, if any weight is negative, or if the
+/// weights do not have a positive sum.
+pub fn weighted_sample(values: &[f64], weights: &[f64], seed: u64) -> f64 {
+    weighted_sample_n(values, weights, 1, seed)[0]
+}
+
+/// Samples `n` values (with replacement) from `values`, where each value is chosen with probability
+/// proportional to its weight, using the random seed `seed`.
+///
+/// # Errors
+/// Panics if `values` and `weights` have different lengths, if any weight is negative, or if the
+/// weights do not have a positive sum.
+pub fn weighted_sample_n(values: &[f64], weights: &[f64], n: usize, seed: u64) -> Vec<f64> {
+    assert_eq!(
+        values.len(),
+        weights.len(),
+        "values and weights must have the same length"
+    );
+    let cumulative = cumulative_weights(weights);
+    alea::set_seed(seed);
+    (0..n).map(|_| values[draw_index(&cumulative)]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_weighted_sample() {
+        let values = [1., 2., 3., 4.];
+        let weights = [1., 0., 3., 6.];
+        let n = 100000;
+        let samples = weighted_sample_n(&values, &weights, n, 17);
+        assert_eq!(samples.len(), n);
+
+        for (v, w) in values.iter().zip(&weights) {
+            let freq = samples.iter().filter(|&x| x == v).count() as f64 / n as f64;
+            assert_approx_eq!(freq, w / 10., 2e-2);
+        }
+        assert!(!samples.contains(&2.));
+
+        assert_eq!(
+            weighted_sample(&values, &weights, 3),
+            weighted_sample(&values, &weights, 3)
+        );
+        assert_eq!(weighted_sample(&values, &[0., 0., 1., 0.], 3), 3.);
+    }
+
+    #[test]
+    #[should_panic(expected = "weights must have a positive sum")]
+    fn test_weighted_sample_zero_weights() {
+        weighted_sample(&[1., 2.], &[0., 0.], 0);
+    }
+}
@@ -20,6 +20,28 @@ pub fn binom_coeff(n: u64, k: u64) -> u64 {
     c
 }
 
+/// Calculates the natural logarithm of the factorial of `n`, `ln(n!)`. This stays finite for
+/// values of `n` where `n!` itself overflows (`n > 170`). Small values are computed by summing
+/// logarithms directly, and larger values with Stirling's series.
+pub fn ln_factorial(n: u64) -> f64 {
+    if n < 256 {
+        (2..=n).map(|i| (i as f64).ln()).sum()
+    } else {
+        let x = n as f64;
+        x * x.ln() - x + 0.5 * (2. * std::f64::consts::PI * x).ln() + 1. / (12. * x)
+            - 1. / (360. * x.powi(3))
+            + 1. / (1260. * x.powi(5))
+    }
+}
+
+/// Calculates the natural logarithm of the binomial coefficient nCk, `ln(n! / (k! (n-k)!))`, for
+/// two integers `n` and `k`, with `n >= k`. Unlike [binom_coeff](crate::functions::binom_coeff),
+/// this does not overflow for large `n`.
+pub fn ln_choose(n: u64, k: u64) -> f64 {
+    assert!(n >= k, "n must be greater than or equal to k");
+    ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k)
+}
+
 /// An alternative method for computing binomial coefficients. There is no significant difference
 /// between the compute time using the `binom_coeff` method and this method. This method becomes
 /// slightly inaccurate (by 1 or 2) starting at `n ~ 50`.
@@ -33,6 +55,7 @@ pub fn binom_coeff_alt(n: u64, k: u64) -> u64 {
 mod tests {
     use super::*;
     use crate::distributions::{DiscreteUniform, Distribution, Distribution1D};
+    use approx_eq::assert_approx_eq;
 
     #[test]
     fn test_binom_methods() {
@@ -50,6 +73,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ln_factorial() {
+        assert_eq!(ln_factorial(0), 0.);
+        assert_eq!(ln_factorial(1), 0.);
+        assert_approx_eq!(ln_factorial(10), 3628800_f64.ln());
+        assert_approx_eq!(ln_factorial(170), 706.5730622457874);
+        assert_approx_eq!(ln_factorial(256), 1167.2572785628802);
+        assert_approx_eq!(ln_factorial(1000), 5912.128178488163);
+        // continuity across the switch to Stirling's series
+        assert_approx_eq!(ln_factorial(256) - ln_factorial(255), 256_f64.ln());
+    }
+
+    #[test]
+    fn test_ln_choose() {
+        for &(n, k) in &[(5, 2), (10, 0), (30, 15), (60, 7)] {
+            assert_approx_eq!(ln_choose(n, k), (binom_coeff(n, k) as f64).ln());
+        }
+        assert!(ln_choose(2000, 1000).is_finite());
+    }
+
     #[test]
     fn test_binom_pascal() {
         let n: Vec<u64> = DiscreteUniform::new(5, 50)
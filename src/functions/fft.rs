@@ -0,0 +1,126 @@
+//! The fast Fourier transform for real input.
+
+use std::f64::consts::PI;
+
+/// Computes the discrete Fourier transform of a complex sequence, given as `(re, im)` pairs, in place
+/// using the iterative radix-2 Cooley-Tukey algorithm. The length of the sequence must be a power of
+/// two. If `inverse` is true, then the inverse transform (including the 1/n normalization) is
+/// computed instead.
+fn fft_inplace(data: &mut [(f64, f64)], inverse: bool) {
+    let n = data.len();
+    assert!(n.is_power_of_two(), "length must be a power of two");
+
+    // bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1. } else { -1. };
+
+    // butterflies, doubling the length of the sub-transforms at each stage
+    let mut len = 2;
+    while len <= n {
+        let theta = sign * 2. * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (wr, wi) = ((theta * k as f64).cos(), (theta * k as f64).sin());
+                let (ur, ui) = data[start + k];
+                let (vr, vi) = data[start + k + len / 2];
+                let (tr, ti) = (vr * wr - vi * wi, vr * wi + vi * wr);
+                data[start + k] = (ur + tr, ui + ti);
+                data[start + k + len / 2] = (ur - tr, ui - ti);
+            }
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        for x in data.iter_mut() {
+            x.0 /= n as f64;
+            x.1 /= n as f64;
+        }
+    }
+}
+
+/// Computes the [discrete Fourier transform](https://en.wikipedia.org/wiki/Discrete_Fourier_transform)
+/// of real input using the fast Fourier transform. The input is zero-padded to the next power of
+/// two. Returns the complex coefficients as `(re, im)` pairs.
+pub fn fft(input: &[f64]) -> Vec<(f64, f64)> {
+    let n = input.len().next_power_of_two();
+    let mut data = input.iter().map(|&x| (x, 0.)).collect::<Vec<_>>();
+    data.resize(n, (0., 0.));
+    fft_inplace(&mut data, false);
+    data
+}
+
+/// Computes the inverse discrete Fourier transform of complex input, given as `(re, im)` pairs,
+/// using the fast Fourier transform. The input is zero-padded to the next power of two. For the
+/// output of [fft](crate::functions::fft), the real parts recover the (padded) original input.
+pub fn ifft(input: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let n = input.len().next_power_of_two();
+    let mut data = input.to_vec();
+    data.resize(n, (0., 0.));
+    fft_inplace(&mut data, true);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_fft_roundtrip() {
+        let x = [
+            0.5, -1.2, 3.3, 0.1, -0.7, 2.2, 1.9, -3.4, 0.0, 4.1, -2.5, 1.1, 0.6, -0.3, 2.8, -1.6,
+        ];
+        let recovered = ifft(&fft(&x));
+        assert_eq!(recovered.len(), x.len());
+        for (r, xi) in recovered.iter().zip(&x) {
+            assert_approx_eq!(r.0, *xi);
+            assert!(r.1.abs() < 1e-12);
+        }
+
+        // non power of two lengths are zero-padded
+        let y = [1., 2., 3., 4., 5.];
+        let recovered = ifft(&fft(&y));
+        assert_eq!(recovered.len(), 8);
+        for (i, r) in recovered.iter().enumerate() {
+            assert!((r.0 - if i < 5 { y[i] } else { 0. }).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_fft_delta() {
+        let mut x = vec![0.; 32];
+        x[0] = 1.;
+        for (re, im) in fft(&x) {
+            assert_approx_eq!(re, 1.);
+            assert!(im.abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_fft_direct() {
+        let x = [1., -2., 0.5, 3., -1., 2.5, 0., 4.];
+        let n = x.len();
+        let coefs = fft(&x);
+        for (k, c) in coefs.iter().enumerate() {
+            let (re, im) = x.iter().enumerate().fold((0., 0.), |(re, im), (t, xt)| {
+                let theta = -2. * PI * (k * t) as f64 / n as f64;
+                (re + xt * theta.cos(), im + xt * theta.sin())
+            });
+            assert!((c.0 - re).abs() < 1e-10);
+            assert!((c.1 - im).abs() < 1e-10);
+        }
+    }
+}
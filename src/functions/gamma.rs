@@ -41,6 +41,146 @@ pub fn gamma(z: f64) -> f64 {
     }
 }
 
+/// Calculates the natural logarithm of the absolute value of the gamma function, using the Lanczos
+/// approximation in log space. Unlike `gamma(x).ln()`, this does not overflow for large `x`. The
+/// reflection formula is used for `x < 0.5`. Returns infinity at the poles (zero and the negative
+/// integers).
+pub fn lgamma(z: f64) -> f64 {
+    if z <= 0. && z == z.floor() {
+        // sin(pi z) is not exactly 0 in floating point, so handle the poles explicitly
+        f64::INFINITY
+    } else if z < 0.5 {
+        (PI / (PI * z).sin().abs()).ln() - lgamma(1. - z)
+    } else {
+        let mut x = 0.99999999999999709182;
+        for (idx, val) in GAMMA_COEFFS.iter().enumerate() {
+            x += val / ((z - 1.) + (idx as f64) + 1.);
+        }
+        let t = (z - 1.) + G - 0.5;
+        0.5 * (2. * PI).ln() + ((z - 1.) + 0.5) * t.ln() - t + x.ln()
+    }
+}
+
+/// Maximum number of terms used in the series and continued fraction for the incomplete gamma
+/// function.
+const GAMMA_INC_MAX_ITER: usize = 1000;
+
+/// Calculates the regularized lower [incomplete gamma
+/// function](https://en.wikipedia.org/wiki/Incomplete_gamma_function) `P(a, x)`, which is the
+/// integral of `t^(a-1) e^(-t)` from 0 to `x`, divided by `gamma(a)`. It is the CDF of a gamma
+/// distribution with shape `a` and rate 1. A power series is used for `x < a + 1`, and a continued
+/// fraction (evaluated with the modified Lentz method) otherwise.
+///
+/// # Errors
+/// Panics if `a` is not positive or `x` is negative.
+pub fn gamma_inc(a: f64, x: f64) -> f64 {
+    assert!(a > 0., "a must be positive");
+    assert!(x >= 0., "x must be non-negative");
+    if x == 0. {
+        return 0.;
+    }
+    // common prefactor x^a e^(-x) / gamma(a)
+    let ln_prefactor = a * x.ln() - x - lgamma(a);
+
+    if x < a + 1. {
+        let mut term = 1. / a;
+        let mut sum = term;
+        for n in 1..GAMMA_INC_MAX_ITER {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * f64::EPSILON {
+                break;
+            }
+        }
+        (sum.ln() + ln_prefactor).exp()
+    } else {
+        let tiny = f64::MIN_POSITIVE / f64::EPSILON;
+        let mut b = x + 1. - a;
+        let mut c = 1. / tiny;
+        let mut d = 1. / b;
+        let mut h = d;
+        for n in 1..GAMMA_INC_MAX_ITER {
+            let an = -(n as f64) * (n as f64 - a);
+            b += 2.;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1. / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.).abs() < f64::EPSILON {
+                break;
+            }
+        }
+        1. - (h.ln() + ln_prefactor).exp()
+    }
+}
+
+/// Calculates the [regularized incomplete beta
+/// function](https://en.wikipedia.org/wiki/Beta_function#Incomplete_beta_function) `I_x(a, b)`,
+/// which is the integral of `t^(a-1) (1-t)^(b-1)` from 0 to `x`, divided by `beta(a, b)`. It is the
+/// CDF of a beta distribution with shape parameters `a` and `b`. It is evaluated with a continued
+/// fraction (using the modified Lentz method), applying the symmetry `I_x(a, b) = 1 - I_(1-x)(b,
+/// a)` where that converges faster.
+///
+/// # Errors
+/// Panics if `a` or `b` is not positive, or if `x` is not in `[0, 1]`.
+pub fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
+    assert!(a > 0. && b > 0., "a and b must be positive");
+    assert!((0. ..=1.).contains(&x), "x must be between 0 and 1");
+    if x == 0. || x == 1. {
+        return x;
+    }
+    // common prefactor x^a (1-x)^b / beta(a, b)
+    let ln_prefactor = lgamma(a + b) - lgamma(a) - lgamma(b) + a * x.ln() + b * (1. - x).ln();
+
+    if x < (a + 1.) / (a + b + 2.) {
+        (ln_prefactor + beta_cf(a, b, x).ln()).exp() / a
+    } else {
+        1. - (ln_prefactor + beta_cf(b, a, 1. - x).ln()).exp() / b
+    }
+}
+
+/// Continued fraction for the incomplete beta function.
+fn beta_cf(a: f64, b: f64, x: f64) -> f64 {
+    let tiny = f64::MIN_POSITIVE / f64::EPSILON;
+    let mut c = 1.;
+    let mut d = 1. - (a + b) * x / (a + 1.);
+    if d.abs() < tiny {
+        d = tiny;
+    }
+    d = 1. / d;
+    let mut h = d;
+    for m in 1..GAMMA_INC_MAX_ITER {
+        let m = m as f64;
+        // even and odd steps of the recurrence
+        for &coeff in &[
+            m * (b - m) * x / ((a + 2. * m - 1.) * (a + 2. * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2. * m) * (a + 2. * m + 1.)),
+        ] {
+            d = 1. + coeff * d;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = 1. + coeff / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1. / d;
+            h *= d * c;
+        }
+        if (d * c - 1.).abs() < f64::EPSILON {
+            break;
+        }
+    }
+    h
+}
+
 /// Calculates the [beta function](https://en.wikipedia.org/wiki/Beta_function) using the
 /// relationship between the beta function and the gamma function.
 pub fn beta(a: f64, b: f64) -> f64 {
@@ -64,9 +204,25 @@ pub fn digamma(x: f64) -> f64 {
     }
 }
 
+/// Calculates the [trigamma function](https://en.wikipedia.org/wiki/Trigamma_function), which is the
+/// derivative of the digamma function. It obeys the equation `trigamma(x+1) = trigamma(x) - 1/x^2`.
+/// The approximation works better for large values. If the value is small, this function will shift
+/// it up using the trigamma recurrence relation.
+pub fn trigamma(x: f64) -> f64 {
+    if x < 6. {
+        trigamma(x + 1.) + 1. / x.powi(2)
+    } else {
+        1. / x + 1. / (2. * x.powi(2)) + 1. / (6. * x.powi(3)) - 1. / (30. * x.powi(5))
+            + 1. / (42. * x.powi(7))
+            - 1. / (30. * x.powi(9))
+            + 5. / (66. * x.powi(11))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::functions::{erf, ln_factorial};
     use approx_eq::assert_approx_eq;
 
     #[test]
@@ -78,6 +234,52 @@ mod tests {
         assert_approx_eq!(gamma(-0.5), -3.54490770181103205459);
     }
 
+    #[test]
+    fn test_lgamma() {
+        assert_approx_eq!(lgamma(0.5), 0.5723649429247001);
+        assert_approx_eq!(lgamma(-0.5), 1.2655121234846454);
+        assert_approx_eq!(lgamma(100.), 359.1342053695754);
+        assert_approx_eq!(lgamma(1e6), 12815504.569147612);
+        assert!(lgamma(1.).abs() < 1e-14);
+        assert!(lgamma(2.).abs() < 1e-14);
+        for &pole in &[0., -1., -2., -7., -150.] {
+            assert_eq!(lgamma(pole), f64::INFINITY);
+        }
+
+        for n in 2..300 {
+            assert_approx_eq!(lgamma(n as f64 + 1.), ln_factorial(n), 1e-12);
+        }
+        for &x in &[0.1, 0.7, 1.5, 3.3, 12.25, 50.5, 120.] {
+            assert_approx_eq!(lgamma(x), gamma(x).ln(), 1e-12);
+        }
+        assert_approx_eq!(lgamma(-2.5), gamma(-2.5).abs().ln(), 1e-12);
+    }
+
+    #[test]
+    fn test_gamma_inc() {
+        assert_eq!(gamma_inc(2.5, 0.), 0.);
+        for &x in &[0.01, 0.5, 1., 3., 10., 40.] {
+            assert_approx_eq!(gamma_inc(1., x), 1. - (-x).exp(), 1e-12);
+            assert_approx_eq!(gamma_inc(0.5, x), erf(x.sqrt()), 1e-6);
+        }
+        assert_approx_eq!(gamma_inc(3., 2.), 0.3233235838169366, 1e-12);
+        assert_approx_eq!(gamma_inc(3., 8.), 1. - 41. * (-8_f64).exp(), 1e-12);
+        assert!(gamma_inc(5., 200.) == 1.);
+    }
+
+    #[test]
+    fn test_beta_inc() {
+        assert_eq!(beta_inc(2., 3., 0.), 0.);
+        assert_eq!(beta_inc(2., 3., 1.), 1.);
+        for &x in &[0.01, 0.3, 0.5, 0.8, 0.99] {
+            assert_approx_eq!(beta_inc(1., 1., x), x, 1e-12);
+            assert_approx_eq!(beta_inc(2.5, 1., x), x.powf(2.5), 1e-12);
+            assert!((beta_inc(4., 7., x) - (1. - beta_inc(7., 4., 1. - x))).abs() < 1e-14);
+        }
+        assert_approx_eq!(beta_inc(2., 3., 0.3), 0.3483, 1e-12);
+        assert_approx_eq!(beta_inc(0.5, 0.5, 0.25), 1. / 3., 1e-12);
+    }
+
     #[test]
     fn test_beta() {
         assert_approx_eq!(beta(1., 3.12345), 1. / 3.12345);
@@ -96,4 +298,12 @@ mod tests {
         assert_approx_eq!(digamma(-0.5), 0.036489973978576520559023667);
         assert_approx_eq!(digamma(1.), -0.57721566490153286060651209);
     }
+
+    #[test]
+    fn test_trigamma() {
+        assert_approx_eq!(trigamma(21. + 1.), trigamma(21.) - 1. / 21_f64.powi(2));
+        assert_approx_eq!(trigamma(1.), PI.powi(2) / 6.);
+        assert_approx_eq!(trigamma(0.5), PI.powi(2) / 2.);
+        assert_approx_eq!(trigamma(3.7), 0.310037857670038);
+    }
 }
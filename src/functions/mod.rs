@@ -1,9 +1,11 @@
 //! Various mathematical and statistical functions.
 
 mod combinatorial;
+mod fft;
 mod gamma;
 mod statistical;
 
 pub use self::combinatorial::*;
+pub use self::fft::*;
 pub use self::gamma::*;
 pub use self::statistical::*;
@@ -13,6 +13,19 @@ pub fn logit(p: f64) -> f64 {
     (p / (1. - p)).ln()
 }
 
+/// Calculates the [softplus function](https://en.wikipedia.org/wiki/Softplus) `ln(1 + exp(x))`,
+/// which is a smooth approximation to [relu]. It is computed in a way that does not overflow for
+/// large `x`.
+pub fn softplus(x: f64) -> f64 {
+    x.max(0.) + (-x.abs()).exp().ln_1p()
+}
+
+/// Calculates the [rectified linear unit](https://en.wikipedia.org/wiki/Rectifier_(neural_networks))
+/// `max(0, x)`.
+pub fn relu(x: f64) -> f64 {
+    x.max(0.)
+}
+
 /// Calculates the one-parameter Box-Cox transformation with some power parameter `lambda`.
 pub fn boxcox(x: f64, lambda: f64) -> f64 {
     assert!(x > 0., "x must be positive");
@@ -68,6 +81,31 @@ pub fn erf(x: f64) -> f64 {
     }
 }
 
+/// Calculates the complementary error function erfc(x) = 1 - erf(x).
+///
+/// # Remarks
+/// Uses the Chebyshev fit from Numerical Recipes (`erfcc`), which has a relative error of less than
+/// about 2e-7 everywhere. Unlike `1 - erf(x)`, this does not lose precision for large `x`.
+#[allow(clippy::excessive_precision)]
+pub fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1. / (1. + 0.5 * z);
+    let poly = -1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807
+                            + t * (-1.13520398
+                                + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let ans = t * (-z * z + poly).exp();
+    if x >= 0. {
+        ans
+    } else {
+        2. - ans
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,6 +150,24 @@ mod tests {
         assert_eq!(logit(1.), f64::INFINITY);
     }
 
+    #[test]
+    fn test_softplus_relu() {
+        for &x in &[-1000., -30., -2., -0.5, 0., 0.5, 2., 30., 1000.] {
+            assert!(softplus(x) > 0. || x < -700.);
+            assert!(softplus(x) >= relu(x));
+            assert!(softplus(x).is_finite());
+        }
+        assert_approx_eq!(softplus(0.), 2_f64.ln());
+        assert_approx_eq!(softplus(1.5), 1.5_f64.exp().ln_1p());
+        assert_approx_eq!(softplus(-3.), (-3_f64).exp().ln_1p());
+        assert_approx_eq!(softplus(40.), 40.);
+        assert_eq!(softplus(1000.), 1000.);
+
+        assert_eq!(relu(-3.), 0.);
+        assert_eq!(relu(0.), 0.);
+        assert_eq!(relu(2.5), 2.5);
+    }
+
     #[test]
     fn test_softmax() {
         let orig = vec![1., 2., 3., 4., 1., 2., 3.];
@@ -125,6 +181,24 @@ mod tests {
         assert_approx_eq!(smv.iter().sum(), 1.);
     }
 
+    #[test]
+    fn test_erfc() {
+        for &(x, expected) in &[
+            (-1.5, 1.9661051464753108),
+            (0., 1.),
+            (0.5, 0.4795001221869535),
+            (2., 0.004677734981047265),
+            (6., 2.1519736712498916e-17),
+            (12., 1.3562611692059042e-64),
+            (26., 5.663192408856143e-296),
+        ] {
+            assert_approx_eq!(erfc(x), expected, 1e-6);
+        }
+        for &x in &[-2., -0.3, 0.1, 0.9, 1.7] {
+            assert!((erfc(x) - (1. - erf(x))).abs() < 1e-6);
+        }
+    }
+
     #[test]
     fn test_erf() {
         assert_approx_eq!(erf(0.), 0., 1e-5);
@@ -102,6 +102,130 @@ const GAUSS_QUAD_WEIGHTS: [f64; 5] = [
     0.0666713443086881,
 ];
 
+/// Integrate a function `f` from `a` to `b` using adaptive [Gauss-Kronrod
+/// quadrature](https://en.wikipedia.org/wiki/Gauss%E2%80%93Kronrod_quadrature_formula). Intervals
+/// are recursively bisected until the difference between the 7-point Gauss and 15-point Kronrod
+/// estimates on each piece is small enough that the total absolute error estimate is below `tol`.
+/// Either limit may be infinite, in which case the integral is computed after a change of
+/// variables onto a finite interval.
+///
+/// # Remarks
+/// Like any adaptive method, this can miss features (e.g., narrow peaks) that fall between all of
+/// the nodes of the initial pieces.
+pub fn quad_adaptive<F>(f: F, a: f64, b: f64, tol: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    assert!(tol > 0., "tolerance must be positive");
+    assert!(!a.is_nan() && !b.is_nan(), "limits must not be NaN");
+    if a == b {
+        return 0.;
+    }
+    if a > b {
+        return -quad_adaptive(f, b, a, tol);
+    }
+
+    match (a.is_finite(), b.is_finite()) {
+        (true, true) => gauss_kronrod_adaptive(&f, a, b, tol, 0),
+        // x = a + t / (1 - t), for t in [0, 1)
+        (true, false) => {
+            let g = |t: f64| {
+                let s = 1. - t;
+                finite_or_zero(f(a + t / s) / (s * s))
+            };
+            gauss_kronrod_adaptive(&g, 0., 1., tol, 0)
+        }
+        // x = b - (1 - t) / t, for t in (0, 1]
+        (false, true) => {
+            let g = |t: f64| finite_or_zero(f(b - (1. - t) / t) / (t * t));
+            gauss_kronrod_adaptive(&g, 0., 1., tol, 0)
+        }
+        // x = t / (1 - t^2), for t in (-1, 1)
+        (false, false) => {
+            let g = |t: f64| {
+                let s = 1. - t * t;
+                finite_or_zero(f(t / s) * (1. + t * t) / (s * s))
+            };
+            gauss_kronrod_adaptive(&g, -1., 1., tol, 0)
+        }
+    }
+}
+
+/// Zeroes out values of a transformed integrand that overflow at the ends of the interval.
+fn finite_or_zero(x: f64) -> f64 {
+    if x.is_finite() {
+        x
+    } else {
+        0.
+    }
+}
+
+/// Every interval is split at least this many times, so that features of the integrand are less
+/// likely to be missed entirely.
+const GK_MIN_DEPTH: usize = 3;
+const GK_MAX_DEPTH: usize = 50;
+
+fn gauss_kronrod_adaptive<F>(f: &F, a: f64, b: f64, tol: f64, depth: usize) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let (kronrod, gauss) = gauss_kronrod15(f, a, b);
+    if depth >= GK_MAX_DEPTH || (depth >= GK_MIN_DEPTH && (kronrod - gauss).abs() <= tol) {
+        return kronrod;
+    }
+    let m = 0.5 * (a + b);
+    gauss_kronrod_adaptive(f, a, m, tol / 2., depth + 1)
+        + gauss_kronrod_adaptive(f, m, b, tol / 2., depth + 1)
+}
+
+/// Returns the 15-point Kronrod and the embedded 7-point Gauss estimates of the integral of `f`
+/// from `a` to `b`.
+fn gauss_kronrod15<F>(f: &F, a: f64, b: f64) -> (f64, f64)
+where
+    F: Fn(f64) -> f64,
+{
+    let xm = 0.5 * (b + a);
+    let xr = 0.5 * (b - a);
+    let fc = f(xm);
+    let mut kronrod = KRONROD_WEIGHTS[7] * fc;
+    let mut gauss = GAUSS_WEIGHTS[3] * fc;
+    for i in 0..7 {
+        let dx = xr * KRONROD_NODES[i];
+        let fsum = f(xm - dx) + f(xm + dx);
+        kronrod += KRONROD_WEIGHTS[i] * fsum;
+        if i % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[i / 2] * fsum;
+        }
+    }
+    (kronrod * xr, gauss * xr)
+}
+
+const KRONROD_NODES: [f64; 7] = [
+    0.9914553711208126,
+    0.9491079123427585,
+    0.8648644233597691,
+    0.7415311855993945,
+    0.5860872354676911,
+    0.4058451513773972,
+    0.20778495500789848,
+];
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022935322010529224,
+    0.06309209262997856,
+    0.10479001032225019,
+    0.14065325971552592,
+    0.1690047266392679,
+    0.19035057806478542,
+    0.20443294007529889,
+    0.20948214108472782,
+];
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.1294849661688697,
+    0.27970539148927664,
+    0.3818300505051189,
+    0.4179591836734694,
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +261,26 @@ mod tests {
         assert_approx_eq!(trapz(f5, 3., 4., 1000), 0.1, 1e-2);
         assert_approx_eq!(romberg(f5, 3., 4., 1e-8, 20), 0.1);
     }
+
+    #[test]
+    fn test_quad_adaptive() {
+        let f1 = |x: f64| x * (1. + 2. * x).sqrt();
+        assert_approx_eq!(quad_adaptive(f1, 4., 0., 1e-10), -298. / 15.);
+
+        let f2 = |x: f64| x.sin().powi(2) * x.cos().powi(2);
+        assert_approx_eq!(quad_adaptive(f2, -2., 2., 1e-10), (8. - 8_f64.sin()) / 16.);
+
+        // integrable singularity at the endpoint
+        let f3 = |x: f64| 1. / x.sqrt();
+        assert_approx_eq!(quad_adaptive(f3, 0., 1., 1e-10), 2., 1e-6);
+
+        let inf = f64::INFINITY;
+        let f4 = |x: f64| (-x * x).exp();
+        assert_approx_eq!(quad_adaptive(f4, -inf, inf, 1e-10), PI.sqrt());
+        assert_approx_eq!(quad_adaptive(f4, 0., inf, 1e-10), PI.sqrt() / 2.);
+        assert_approx_eq!(quad_adaptive(f4, -inf, 0., 1e-10), PI.sqrt() / 2.);
+
+        let f5 = |x: f64| 1. / (1. + x * x);
+        assert_approx_eq!(quad_adaptive(f5, 1., inf, 1e-10), PI / 4.);
+    }
 }
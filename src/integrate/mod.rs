@@ -1,7 +1,9 @@
 //! Algorithms for integrating functions and ODEs.
 
 mod functions;
+mod monte_carlo;
 // mod odes;
 
 pub use functions::*;
+pub use monte_carlo::*;
 // pub use odes::*;
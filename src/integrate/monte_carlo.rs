@@ -0,0 +1,149 @@
+//! Monte Carlo and quasi-Monte Carlo integration of multidimensional functions.
+
+/// How the points at which the integrand is evaluated are chosen by [monte_carlo_nd].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sampling {
+    /// Independent uniformly distributed points from the global random number generator.
+    Random,
+    /// Points from the [Halton sequence](halton), giving quasi-Monte Carlo integration.
+    Halton,
+}
+
+/// Generates the first `n` points of the [Halton
+/// sequence](https://en.wikipedia.org/wiki/Halton_sequence) in `[0, 1]^dim`, using the first `dim`
+/// primes as the bases. The points are returned row-major, so that point `i` is
+/// `points[i * dim..(i + 1) * dim]`. The sequence starts from index 1, skipping the origin.
+///
+/// # Remarks
+/// The coordinates in high dimensions (large primes) are strongly correlated for small `n`, so the
+/// sequence works best in a moderate number of dimensions.
+pub fn halton(n: usize, dim: usize) -> Vec<f64> {
+    let bases = primes(dim);
+    let mut points = Vec::with_capacity(n * dim);
+    for i in 1..=n {
+        points.extend(bases.iter().map(|&b| radical_inverse(i, b)));
+    }
+    points
+}
+
+/// Reflects the digits of `i` in base `b` about the radix point.
+fn radical_inverse(mut i: usize, b: usize) -> f64 {
+    let inv_b = 1. / b as f64;
+    let mut f = inv_b;
+    let mut r = 0.;
+    while i > 0 {
+        r += f * (i % b) as f64;
+        i /= b;
+        f *= inv_b;
+    }
+    r
+}
+
+/// Returns the first `n` primes.
+fn primes(n: usize) -> Vec<usize> {
+    let mut primes: Vec<usize> = Vec::with_capacity(n);
+    let mut candidate = 2;
+    while primes.len() < n {
+        if primes
+            .iter()
+            .take_while(|&&p| p * p <= candidate)
+            .all(|&p| candidate % p != 0)
+        {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+    primes
+}
+
+/// Integrate a function `f` of several variables over the box with corners `lower` and `upper`
+/// by averaging it over `n` points chosen according to `sampling`. The error of plain Monte Carlo
+/// integration decreases like `1/sqrt(n)`, while for smooth integrands quasi-Monte Carlo
+/// integration with [Sampling::Halton] converges at close to `1/n`.
+///
+/// # Errors
+/// Panics if `lower` and `upper` have different lengths or if `n` is zero.
+pub fn monte_carlo_nd<F>(f: F, lower: &[f64], upper: &[f64], n: usize, sampling: Sampling) -> f64
+where
+    F: Fn(&[f64]) -> f64,
+{
+    assert_eq!(
+        lower.len(),
+        upper.len(),
+        "lower and upper must have the same length"
+    );
+    assert!(n > 0, "need at least one point");
+    let dim = lower.len();
+    let widths = lower
+        .iter()
+        .zip(upper)
+        .map(|(l, u)| u - l)
+        .collect::<Vec<_>>();
+    let volume = widths.iter().product::<f64>();
+
+    let unit = match sampling {
+        Sampling::Random => (0..n * dim).map(|_| alea::f64()).collect::<Vec<_>>(),
+        Sampling::Halton => halton(n, dim),
+    };
+
+    let mut x = vec![0.; dim];
+    let total = unit
+        .chunks(dim.max(1))
+        .take(n)
+        .map(|u| {
+            for j in 0..dim {
+                x[j] = lower[j] + widths[j] * u[j];
+            }
+            f(&x)
+        })
+        .sum::<f64>();
+    volume * total / n as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_halton() {
+        assert_eq!(primes(6), vec![2, 3, 5, 7, 11, 13]);
+
+        let points = halton(1000, 5);
+        assert_eq!(points.len(), 5000);
+        assert!(points.iter().all(|&p| (0. ..=1.).contains(&p)));
+
+        let points = halton(4, 2);
+        let expected = [0.5, 1. / 3., 0.25, 2. / 3., 0.75, 1. / 9., 0.125, 4. / 9.];
+        for (p, e) in points.iter().zip(&expected) {
+            assert_approx_eq!(*p, *e);
+        }
+    }
+
+    #[test]
+    fn test_monte_carlo_nd() {
+        let f = |x: &[f64]| (x[0] + x[1] + x[2]).exp();
+        let (lower, upper) = ([0., 0., -1.], [1., 2., 0.]);
+        let exact = (1_f64.exp() - 1.) * (2_f64.exp() - 1.) * (1. - (-1_f64).exp());
+        let n = 4096;
+
+        let qmc_err = (monte_carlo_nd(f, &lower, &upper, n, Sampling::Halton) - exact).abs();
+
+        // root mean squared error of plain Monte Carlo over several seeds
+        let mc_err = ((0..10)
+            .map(|seed| {
+                alea::set_seed(seed);
+                (monte_carlo_nd(f, &lower, &upper, n, Sampling::Random) - exact).powi(2)
+            })
+            .sum::<f64>()
+            / 10.)
+            .sqrt();
+
+        assert_approx_eq!(
+            monte_carlo_nd(f, &lower, &upper, n, Sampling::Random),
+            exact,
+            0.05
+        );
+        assert!(qmc_err < mc_err / 5.);
+    }
+}
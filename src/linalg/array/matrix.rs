@@ -1,5 +1,6 @@
 use std::{
     fmt::{Display, Formatter, Result},
+    iter::FromIterator,
     mem::swap,
     ops::{Index, IndexMut, Neg},
     panic,
@@ -9,12 +10,14 @@ use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
 use crate::prelude::{transpose, Dot};
 
-use super::super::utils::{dot, ipiv_parity};
+use super::super::decomposition::sqrtm_symmetric;
+use super::super::utils::{dot, ipiv_parity, norm};
 use super::vops::*;
 use super::{broadcast_add, broadcast_div, broadcast_mul, broadcast_sub, Vector};
 
 /// Matrix struct.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Matrix {
     data: Vector,
     pub nrows: usize,
@@ -86,6 +89,13 @@ impl Matrix {
         self.data.close_to(&other.data, tol)
     }
 
+    /// Check whether the matrix has the same shape as another matrix and every element is within
+    /// an absolute tolerance `tol` of the corresponding element. See
+    /// [Vector::approx_eq](crate::linalg::Vector::approx_eq).
+    pub fn approx_eq(&self, other: &Matrix, tol: f64) -> bool {
+        self.shape() == other.shape() && self.data.approx_eq(&other.data, tol)
+    }
+
     /// Check whether the matrix is positive definite.
     pub fn is_positive_definite(&self) -> bool {
         if self.is_symmetric() {
@@ -145,6 +155,41 @@ impl Matrix {
         l
     }
 
+    /// Orthonormalize the columns of the matrix using the modified [Gram-Schmidt
+    /// process](https://en.wikipedia.org/wiki/Gram%E2%80%93Schmidt_process). The columns of the
+    /// result are orthonormal and span the same space as the columns of the original matrix.
+    ///
+    /// # Remarks
+    /// Columns which are (numerically) linearly dependent on the preceding columns are set to zero
+    /// in the result, so that the output has the same shape as the input.
+    pub fn gram_schmidt(&self) -> Matrix {
+        // work on the transpose so that each column is a contiguous row
+        let mut q = self.t();
+        let n = self.nrows;
+
+        for j in 0..self.ncols {
+            let orig_norm = norm(&q.data[j * n..(j + 1) * n]);
+            for k in 0..j {
+                let r = dot(&q.data[k * n..(k + 1) * n], &q.data[j * n..(j + 1) * n]);
+                for i in 0..n {
+                    q.data[j * n + i] -= r * q.data[k * n + i];
+                }
+            }
+            let new_norm = norm(&q.data[j * n..(j + 1) * n]);
+            let scale = if new_norm > 1e-10 * orig_norm {
+                1. / new_norm
+            } else {
+                0.
+            };
+            q.data[j * n..(j + 1) * n]
+                .iter_mut()
+                .for_each(|x| *x *= scale);
+        }
+
+        q.t_mut();
+        q
+    }
+
     pub fn lu(&self) -> (Matrix, Vec<i32>) {
         assert!(self.is_square(), "matrix not square");
 
@@ -229,11 +274,79 @@ impl Matrix {
         let n = self.nrows.min(self.ncols);
         let mut diag = Vector::with_capacity(n);
         for i in 0..n {
-            diag.push(self.data[i * n + i]);
+            diag.push(self.data[i * self.ncols + i]);
         }
         diag
     }
 
+    /// Make a square matrix with the elements of `v` on the diagonal and zeros elsewhere.
+    pub fn from_diag(v: &Vector) -> Self {
+        let n = v.len();
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m.data[i * n + i] = v[i];
+        }
+        m
+    }
+
+    /// Raise the matrix to the `n`th power using exponentiation by squaring. Returns the identity
+    /// matrix if `n == 0`.
+    pub fn pow(&self, n: usize) -> Self {
+        assert!(self.is_square(), "matrix not square");
+        let mut result = Matrix::eye(self.nrows);
+        let mut base = self.clone();
+        let mut n = n;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.dot(&base);
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.dot(&base);
+            }
+        }
+        result
+    }
+
+    /// Calculate the trace of the matrix, which is the sum of the diagonal elements.
+    pub fn trace(&self) -> f64 {
+        assert!(self.is_square(), "matrix not square");
+        self.diag().sum()
+    }
+
+    /// Calculate the sample covariance matrix of the columns of the matrix, treating each row as
+    /// an observation.
+    pub fn covariance(&self) -> Self {
+        assert!(self.nrows > 1, "need at least two observations");
+        let centered = self.center_cols();
+        centered.t_dot(&centered) / (self.nrows - 1) as f64
+    }
+
+    /// Whiten the matrix, treating each row as an observation. The columns are centered and then
+    /// decorrelated by multiplying by the symmetric inverse square root of the
+    /// [covariance](Matrix::covariance) matrix, so that the result has an identity covariance
+    /// matrix.
+    pub fn whiten(&self) -> Self {
+        let cov = self.covariance();
+        let root = Matrix::new(
+            sqrtm_symmetric(&cov.data),
+            self.ncols as i32,
+            self.ncols as i32,
+        );
+        self.center_cols().dot(&root.inv())
+    }
+
+    fn center_cols(&self) -> Self {
+        let means = self.sum_cols() / self.nrows as f64;
+        let mut centered = self.clone();
+        for row in 0..self.nrows {
+            for col in 0..self.ncols {
+                centered[row][col] -= means[col];
+            }
+        }
+        centered
+    }
+
     /// Make a new matrix with the given number of rows and columns.
     pub fn new<T>(data: T, nrows: i32, ncols: i32) -> Self
     where
@@ -253,6 +366,29 @@ impl Matrix {
         m
     }
 
+    /// Make a new matrix with the given number of rows and columns by copying the data from a
+    /// slice, in row-major order.
+    ///
+    /// # Errors
+    /// Panics if the length of the data is not `nrows * ncols`.
+    pub fn from_slice(data: &[f64], nrows: usize, ncols: usize) -> Self {
+        assert_eq!(
+            data.len(),
+            nrows * ncols,
+            "data length does not match the number of rows and columns"
+        );
+        Self {
+            data: Vector::from(data),
+            nrows,
+            ncols,
+        }
+    }
+
+    /// Iterate over the rows of the matrix.
+    pub fn rows(&self) -> impl Iterator<Item = &[f64]> {
+        self.data.chunks(self.ncols.max(1))
+    }
+
     /// Get the number of rows and columns in the matrix.
     pub fn shape(&self) -> [usize; 2] {
         [self.nrows, self.ncols]
@@ -389,6 +525,28 @@ impl Matrix {
         Matrix::new(t, self.ncols as i32, self.nrows as i32)
     }
 
+    /// Transpose the matrix, returning a new `ncols x nrows` matrix. This is the same as
+    /// [t](Matrix::t), and works for matrices of any shape.
+    pub fn transpose(&self) -> Self {
+        self.t()
+    }
+
+    /// Transpose a square matrix in-place by swapping elements across the diagonal, without
+    /// allocating. Use [t_mut](Matrix::t_mut) for matrices which are not square.
+    ///
+    /// # Errors
+    /// Panics if the matrix is not square.
+    pub fn transpose_square_mut(&mut self) -> &mut Self {
+        assert!(self.is_square(), "matrix not square");
+        let n = self.nrows;
+        for i in 0..n {
+            for j in (i + 1)..n {
+                self.data.swap(i * n + j, j * n + i);
+            }
+        }
+        self
+    }
+
     /// Transpose the matrix in-place.
     pub fn t_mut(&mut self) -> &mut Self {
         let t = transpose(&self.data, self.nrows);
@@ -412,6 +570,11 @@ impl Matrix {
         self.data
     }
 
+    /// Returns a Vector with the elements of the matrix in row-major order.
+    pub fn flatten(&self) -> Vector {
+        self.data.clone()
+    }
+
     /// Horizontal concatenation of matrices. Adds `other` to the right of the calling matrix.
     pub fn hcat(&self, other: Self) -> Self {
         assert_eq!(self.nrows, other.nrows);
@@ -597,6 +760,64 @@ impl<'a> IntoIterator for &'a mut Matrix {
     }
 }
 
+impl From<(Vec<f64>, usize, usize)> for Matrix {
+    /// Make a matrix from a tuple of the data (in row-major order), the number of rows, and the
+    /// number of columns.
+    ///
+    /// # Errors
+    /// Panics if the length of the data is not `nrows * ncols`.
+    fn from((data, nrows, ncols): (Vec<f64>, usize, usize)) -> Self {
+        assert_eq!(
+            data.len(),
+            nrows * ncols,
+            "data length does not match the number of rows and columns"
+        );
+        Self {
+            data: Vector::from(data),
+            nrows,
+            ncols,
+        }
+    }
+}
+
+impl FromIterator<Vec<f64>> for Matrix {
+    /// Make a matrix from an iterator over its rows.
+    ///
+    /// # Errors
+    /// Panics if the rows do not all have the same length.
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = Vec<f64>>,
+    {
+        let mut data = Vec::new();
+        let mut nrows = 0;
+        let mut ncols = 0;
+        for row in iter {
+            if nrows == 0 {
+                ncols = row.len();
+            }
+            assert_eq!(row.len(), ncols, "rows must all have the same length");
+            data.extend(row);
+            nrows += 1;
+        }
+        Self {
+            data: Vector::from(data),
+            nrows,
+            ncols,
+        }
+    }
+}
+
+impl From<Vec<Vec<f64>>> for Matrix {
+    /// Make a matrix from a vector of its rows.
+    ///
+    /// # Errors
+    /// Panics if the rows do not all have the same length.
+    fn from(rows: Vec<Vec<f64>>) -> Self {
+        rows.into_iter().collect()
+    }
+}
+
 impl Default for Matrix {
     fn default() -> Self {
         Self::empty()
@@ -874,6 +1095,8 @@ impl_reduction_fns_matrix!(norm, max, mean, min, std, sum, prod, var, sample_std
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::distributions::{Distribution1D, Normal};
+    use crate::statistics::sample_covariance;
 
     #[test]
     fn test_mat_vec_ops() {
@@ -988,4 +1211,202 @@ mod tests {
             1e-10
         ));
     }
+
+    #[test]
+    fn test_gram_schmidt() {
+        let a = Matrix::new(
+            [
+                2., -1., 0.5, 0., 3., 1., 1., 1., -2., 4., 0.5, 2., -1., 2., 1.,
+            ],
+            5,
+            3,
+        );
+        let q = a.gram_schmidt();
+        assert_eq!(q.shape(), [5, 3]);
+        let qtq = q.t_dot(&q);
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1. } else { 0. };
+                assert!((qtq[[i, j]] - expected).abs() < 1e-12);
+            }
+        }
+
+        // already orthonormal, so applying it again should not change anything
+        let qq = q.gram_schmidt();
+        for i in 0..q.size() {
+            assert!((qq.flat_idx(i) - q.flat_idx(i)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_gram_schmidt_dependent() {
+        // the second column is twice the first
+        let a = Matrix::new([1., 2., 0., 1., 2., 1., 1., 2., 3.], 3, 3);
+        let q = a.gram_schmidt();
+        for i in 0..3 {
+            assert!(q[[i, 1]].abs() < 1e-12);
+        }
+        let qtq = q.t_dot(&q);
+        assert!((qtq[[0, 0]] - 1.).abs() < 1e-12);
+        assert!((qtq[[2, 2]] - 1.).abs() < 1e-12);
+        assert!(qtq[[0, 2]].abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_from_slice_rows() {
+        let data = [1., 2., 3., 4., 5., 6.];
+        let m = Matrix::from_slice(&data, 2, 3);
+        assert_eq!(m.shape(), [2, 3]);
+        assert_eq!(m, Matrix::new(data, 2, 3));
+        assert_eq!(m, Matrix::from((data.to_vec(), 2, 3)));
+
+        let rows = m.rows().collect::<Vec<_>>();
+        assert_eq!(rows, vec![&[1., 2., 3.][..], &[4., 5., 6.][..]]);
+
+        let rebuilt = m.rows().flatten().copied().collect::<Vec<_>>();
+        assert_eq!(Matrix::from_slice(&rebuilt, m.nrows, m.ncols), m);
+
+        assert_eq!(Matrix::empty().rows().count(), 0);
+    }
+
+    #[test]
+    fn test_diag_trace() {
+        let v = Vector::new([1.5, -2., 4.]);
+        let d = Matrix::from_diag(&v);
+        assert_eq!(d.shape(), [3, 3]);
+        assert_eq!(d.diag(), v);
+        assert_eq!(d.trace(), 3.5);
+        assert!(d.is_symmetric());
+
+        let m = Matrix::new([1., 2., 3., 4., 5., 6.], 2, 3);
+        assert_eq!(m.diag(), Vector::new([1., 5.]));
+        assert!(!m.is_symmetric());
+
+        let a = Matrix::new([4., 1., -2., 1., 3., 0.5, -2., 0.5, 6.], 3, 3);
+        assert!(a.is_symmetric());
+        assert_eq!(a.trace(), 13.);
+        let b = Matrix::new([4., 1., -2., 1., 3., 0.5, 2., 0.5, 6.], 3, 3);
+        assert!(!b.is_symmetric());
+    }
+
+    #[test]
+    fn test_pow() {
+        let a = Matrix::new([1., 2., -0.5, 0.3, 1.5, 2., -1., 0.2, 0.7], 3, 3);
+        assert_eq!(a.pow(0), Matrix::eye(3));
+        assert_eq!(a.pow(1), a);
+        assert!(a.pow(3).close_to(&a.dot(&a).dot(&a), 1e-12));
+        assert!(a.pow(6).close_to(&a.pow(2).pow(3), 1e-12));
+
+        // rows of a high power of a transition matrix approach the stationary distribution
+        let p = Matrix::new([0.9, 0.1, 0.5, 0.5], 2, 2);
+        let limit = p.pow(100);
+        for i in 0..2 {
+            assert!((limit[[i, 0]] - 5. / 6.).abs() < 1e-12);
+            assert!((limit[[i, 1]] - 1. / 6.).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_transpose() {
+        let a = Matrix::new([1., 2., 3., 4., 5., 6.], 2, 3);
+        let at = a.transpose();
+        assert_eq!(at.shape(), [3, 2]);
+        for i in 0..2 {
+            for j in 0..3 {
+                assert_eq!(at[[j, i]], a[[i, j]]);
+            }
+        }
+        assert_eq!(at.transpose(), a);
+
+        let mut b = Matrix::new([1., 2., 3., 4., 5., 6., 7., 8., 9.], 3, 3);
+        let bt = b.t();
+        b.transpose_square_mut();
+        assert_eq!(b, bt);
+        b.transpose_square_mut();
+        assert_eq!(b.t(), bt);
+    }
+
+    #[test]
+    #[should_panic(expected = "matrix not square")]
+    fn test_transpose_square_mut_rectangular() {
+        Matrix::new([1., 2., 3., 4., 5., 6.], 2, 3).transpose_square_mut();
+    }
+
+    #[test]
+    fn test_covariance_whiten() {
+        alea::set_seed(5);
+        let n = 200;
+        let z = Normal::new(0., 1.).sample_n(3 * n);
+        let mut data = Vec::with_capacity(3 * n);
+        for row in z.chunks(3) {
+            data.extend_from_slice(&[
+                2. + row[0],
+                -1. + 0.8 * row[0] + 0.5 * row[1],
+                3. * row[2] - row[1],
+            ]);
+        }
+        let m = Matrix::new(data, n as i32, 3);
+
+        let cov = m.covariance();
+        assert!(cov.is_symmetric());
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = sample_covariance(&m.get_col_as_vector(i), &m.get_col_as_vector(j));
+                assert!((cov[[i, j]] - expected).abs() < 1e-12);
+            }
+        }
+
+        let w = m.whiten();
+        assert!(w.covariance().close_to(&Matrix::eye(3), 1e-10));
+        assert!(w.sum_cols().iter().all(|x| x.abs() < 1e-10));
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let a = Matrix::new([1., 2., 0., -4., 5., 6.], 2, 3);
+        let perturbed = &a + &Matrix::new([1e-10, -1e-10, 1e-10, 0., 0., -1e-10], 2, 3);
+        assert!(a.approx_eq(&perturbed, 1e-9));
+        assert!(!a.approx_eq(&perturbed, 1e-11));
+        assert!(!a.approx_eq(&a.reshape(3, 2), 1e-9));
+        assert!(!a.approx_eq(&a.t(), 1e-9));
+
+        // e.g., checking a decomposition
+        let b = Matrix::new([4., 2., 2., 3.], 2, 2);
+        let l = b.cholesky();
+        assert!(l.dot(&l.t()).approx_eq(&b, 1e-12));
+    }
+
+    #[test]
+    fn test_reshape_from_rows_flatten() {
+        let a = Matrix::from(vec![vec![1., 2., 3.], vec![4., 5., 6.]]);
+        assert_eq!(a.shape(), [2, 3]);
+        assert_eq!(a[[1, 0]], 4.);
+
+        let b = a.reshape(3, 2);
+        assert_eq!(b.shape(), [3, 2]);
+        assert_eq!(b.get_row_as_vector(1).to_vec(), vec![3., 4.]);
+        assert_eq!(b.flatten(), a.flatten());
+        assert_eq!(a.flatten().to_vec(), vec![1., 2., 3., 4., 5., 6.]);
+
+        let c = (0..3).map(|i| vec![i as f64; 2]).collect::<Matrix>();
+        assert_eq!(c, Matrix::new([0., 0., 1., 1., 2., 2.], 3, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "rows must all have the same length")]
+    fn test_from_rows_ragged() {
+        let _ = Matrix::from(vec![vec![1., 2., 3.], vec![4., 5.]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid shape")]
+    fn test_reshape_bad_size() {
+        Matrix::new([1., 2., 3., 4., 5., 6.], 2, 3).reshape(4, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "data length does not match")]
+    fn test_from_slice_bad_length() {
+        Matrix::from_slice(&[1., 2., 3., 4., 5.], 2, 3);
+    }
 }
@@ -1,6 +1,12 @@
 use super::{vops::*, Matrix};
-use crate::linalg::{logmeanexp, logsumexp, norm, prod, sum};
-use crate::statistics::{argmax, argmin, max, mean, min, sample_std, sample_var, std, var};
+use crate::functions::{relu, softplus};
+use crate::linalg::{
+    clip, cosine_similarity, dot, euclidean_distance, logmeanexp, logsumexp, norm, prod, sum,
+};
+use crate::statistics::{
+    argmax, argmin, argsort, argsort_desc, cummax, cummin, describe, max, mean, min, nan_last_cmp,
+    sample_std, sample_var, std, var, Summary,
+};
 use approx_eq::rel_diff;
 use std::convert::From;
 use std::fmt::{Display, Formatter, Result};
@@ -9,6 +15,7 @@ use std::ops::{self, Deref, DerefMut, Neg};
 
 /// A row-major ordering vector struct with various useful methods.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector {
     v: Vec<f64>,
 }
@@ -52,6 +59,111 @@ impl Vector {
         Matrix::new(self.clone(), nrows, ncols)
     }
 
+    /// Calculates the inner product of two vectors, returning a scalar. Unlike
+    /// [Dot](crate::linalg::Dot), this does not return a 1-element vector.
+    ///
+    /// # Errors
+    /// Panics if the vectors have different lengths.
+    pub fn dot_scalar(&self, other: &Vector) -> f64 {
+        assert_eq!(self.len(), other.len(), "vectors must have the same length");
+        dot(&self.v, &other.v)
+    }
+
+    /// Calculates the cosine similarity with another vector. See
+    /// [cosine_similarity](crate::linalg::cosine_similarity).
+    pub fn cosine_similarity(&self, other: &Vector) -> f64 {
+        cosine_similarity(&self.v, &other.v)
+    }
+
+    /// Calculates the Euclidean distance to another vector. See
+    /// [euclidean_distance](crate::linalg::euclidean_distance).
+    pub fn euclidean_distance(&self, other: &Vector) -> f64 {
+        euclidean_distance(&self.v, &other.v)
+    }
+
+    /// Scales the vector to have unit (L2) norm. A zero vector is returned unchanged.
+    pub fn normalize(&self) -> Vector {
+        let n = self.norm();
+        if n == 0. {
+            self.clone()
+        } else {
+            self / n
+        }
+    }
+
+    /// Scales the vector so that the sum of the absolute values of its elements is one. A zero
+    /// vector is returned unchanged.
+    pub fn normalize_l1(&self) -> Vector {
+        let n = self.iter().map(|x| x.abs()).sum::<f64>();
+        if n == 0. {
+            self.clone()
+        } else {
+            self / n
+        }
+    }
+
+    /// Clamps each element of the vector into the range `[min, max]`. See
+    /// [clip](crate::linalg::clip).
+    pub fn clip(&self, min: f64, max: f64) -> Vector {
+        Vector::from(clip(&self.v, min, max))
+    }
+
+    /// Applies the [softplus](crate::functions::softplus) function to each element of the vector.
+    pub fn softplus(&self) -> Vector {
+        self.iter().map(|&x| softplus(x)).collect()
+    }
+
+    /// Applies the [relu](crate::functions::relu) function to each element of the vector.
+    pub fn relu(&self) -> Vector {
+        self.iter().map(|&x| relu(x)).collect()
+    }
+
+    /// Returns the indices that would sort the vector in ascending order. See
+    /// [argsort](crate::statistics::argsort).
+    pub fn argsort(&self) -> Vec<usize> {
+        argsort(&self.v)
+    }
+
+    /// Returns the indices that would sort the vector in descending order. See
+    /// [argsort_desc](crate::statistics::argsort_desc).
+    pub fn argsort_desc(&self) -> Vec<usize> {
+        argsort_desc(&self.v)
+    }
+
+    /// Returns the running maximum of the vector. See [cummax](crate::statistics::cummax).
+    pub fn cummax(&self) -> Vector {
+        Vector::from(cummax(&self.v))
+    }
+
+    /// Returns the running minimum of the vector. See [cummin](crate::statistics::cummin).
+    pub fn cummin(&self) -> Vector {
+        Vector::from(cummin(&self.v))
+    }
+
+    /// Calculates summary statistics of the vector. See [describe](crate::statistics::describe).
+    pub fn describe(&self) -> Summary {
+        describe(&self.v)
+    }
+
+    /// Returns a copy of the vector sorted in ascending order, with any NaNs placed at the end.
+    pub fn sorted(&self) -> Vector {
+        let mut sorted = self.v.clone();
+        sorted.sort_by(|a, b| nan_last_cmp(a, b, false));
+        Vector::from(sorted)
+    }
+
+    /// Returns a copy of the vector sorted in descending order, with any NaNs placed at the end.
+    pub fn sorted_desc(&self) -> Vector {
+        let mut sorted = self.v.clone();
+        sorted.sort_by(|a, b| nan_last_cmp(a, b, true));
+        Vector::from(sorted)
+    }
+
+    /// Returns a copy of the vector with the elements in reverse order.
+    pub fn reversed(&self) -> Vector {
+        self.iter().rev().copied().collect()
+    }
+
     pub fn close_to(&self, other: &Vector, tol: f64) -> bool {
         if self.len() != other.len() {
             return false;
@@ -63,6 +175,14 @@ impl Vector {
         }
         true
     }
+
+    /// Check whether every element of the vector is within an absolute tolerance `tol` of the
+    /// corresponding element of another vector of the same length. Unlike
+    /// [close_to](Vector::close_to), which compares relative differences, this also behaves well
+    /// for elements that are near zero.
+    pub fn approx_eq(&self, other: &Vector, tol: f64) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| (a - b).abs() <= tol)
+    }
 }
 
 impl Default for Vector {
@@ -379,3 +499,109 @@ macro_rules! impl_inner_fn {
 impl_inner_fn!(f64 for norm, max, mean, min, std, sum, prod, var, sample_std, sample_var, logsumexp, logmeanexp);
 
 impl_inner_fn!(usize for argmin, argmax);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dot_scalar() {
+        let a = Vector::new([1., 2., 3.]);
+        let b = Vector::new([4., 5., 6.]);
+        assert_eq!(a.dot_scalar(&b), 32.);
+        assert_eq!(b.dot_scalar(&a), 32.);
+    }
+
+    #[test]
+    fn test_similarity_distance() {
+        let a = Vector::new([3., 4.]);
+        let b = Vector::new([-4., 3.]);
+        assert_eq!(a.cosine_similarity(&b), 0.);
+        assert_eq!(a.euclidean_distance(&a), 0.);
+        assert_eq!(a.euclidean_distance(&Vector::zeros(2)), 5.);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let v = Vector::new([3., -4., 12.]);
+        let unit = v.normalize();
+        assert!((unit.norm() - 1.).abs() < 1e-12);
+        assert_eq!(unit, Vector::new([3. / 13., -4. / 13., 12. / 13.]));
+
+        let l1 = v.normalize_l1();
+        assert!((l1.iter().map(|x| x.abs()).sum::<f64>() - 1.).abs() < 1e-12);
+        assert_eq!(l1, Vector::new([3. / 19., -4. / 19., 12. / 19.]));
+
+        let zero = Vector::zeros(3);
+        assert_eq!(zero.normalize(), zero);
+        assert_eq!(zero.normalize_l1(), zero);
+    }
+
+    #[test]
+    fn test_argsort() {
+        let x = Vector::new([2., -1., 2., 0.5]);
+        assert_eq!(x.argsort(), vec![1, 3, 0, 2]);
+        assert_eq!(x.argsort_desc(), vec![0, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_clip() {
+        let x = Vector::new([-2., 0.5, 3.]);
+        assert_eq!(x.clip(0., 1.), Vector::new([0., 0.5, 1.]));
+    }
+
+    #[test]
+    fn test_softplus_relu() {
+        let x = Vector::new([-3., 0., 2.]);
+        assert_eq!(x.relu(), Vector::new([0., 0., 2.]));
+        let y = x.softplus();
+        assert_eq!(y.len(), 3);
+        assert!(y.iter().all(|&v| v > 0.));
+        assert_eq!(y[1], 2_f64.ln());
+    }
+
+    #[test]
+    fn test_cummax_cummin() {
+        let x = Vector::new([1., 3., 2., 0.]);
+        assert_eq!(x.cummax(), Vector::new([1., 3., 3., 3.]));
+        assert_eq!(x.cummin(), Vector::new([1., 1., 1., 0.]));
+    }
+
+    #[test]
+    fn test_describe() {
+        let x = Vector::new([1., 3., 2., 0.]);
+        let summary = x.describe();
+        assert_eq!(summary, describe(&x));
+        assert_eq!((summary.count, summary.median), (4, 1.5));
+    }
+
+    #[test]
+    fn test_sorted_reversed() {
+        let x = Vector::new([2., -1., 3.5, 0.5]);
+        assert_eq!(x.sorted().to_vec(), vec![-1., 0.5, 2., 3.5]);
+        assert_eq!(x.sorted_desc().to_vec(), vec![3.5, 2., 0.5, -1.]);
+        assert_eq!(x.reversed().to_vec(), vec![0.5, 3.5, -1., 2.]);
+        assert_eq!(x.to_vec(), vec![2., -1., 3.5, 0.5]);
+
+        let y = Vector::new([f64::NAN, 1., f64::NAN, -2.]);
+        for sorted in &[y.sorted(), y.sorted_desc()] {
+            assert!(sorted[2].is_nan() && sorted[3].is_nan());
+        }
+        assert_eq!(&y.sorted()[..2], &[-2., 1.]);
+        assert_eq!(&y.sorted_desc()[..2], &[1., -2.]);
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let x = Vector::new([1., 0., -2.5]);
+        assert!(x.approx_eq(&Vector::new([1. + 1e-9, -1e-9, -2.5]), 1e-8));
+        assert!(!x.approx_eq(&Vector::new([1. + 1e-6, 0., -2.5]), 1e-8));
+        assert!(!x.approx_eq(&Vector::new([1., 0.]), 1e-8));
+    }
+
+    #[test]
+    #[should_panic(expected = "vectors must have the same length")]
+    fn test_dot_scalar_mismatched() {
+        Vector::new([1., 2., 3.]).dot_scalar(&Vector::new([1., 2.]));
+    }
+}
@@ -0,0 +1,173 @@
+//! Implements eigendecomposition of symmetric matrices using the [Jacobi eigenvalue
+//! algorithm](https://en.wikipedia.org/wiki/Jacobi_eigenvalue_algorithm).
+
+use crate::linalg::{is_square, is_symmetric};
+
+/// Computes the eigenvalues and eigenvectors of the symmetric matrix `a` using cyclic Jacobi
+/// rotations. Returns `(eigenvalues, eigenvectors)`, where the eigenvalues are sorted in ascending
+/// order and the eigenvectors are the columns of the (row-major, orthogonal) matrix
+/// `eigenvectors`, in the same order as the eigenvalues.
+pub fn eigen_symmetric(a: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    assert!(is_symmetric(a), "matrix not symmetric");
+    let n = is_square(a).unwrap();
+
+    let mut a = a.to_vec();
+    let mut v = vec![0.; n * n];
+    for i in 0..n {
+        v[i * n + i] = 1.;
+    }
+
+    let total = a.iter().map(|x| x * x).sum::<f64>();
+
+    for _ in 0..100 {
+        let mut off = 0.;
+        for p in 0..n {
+            for q in (p + 1)..n {
+                off += a[p * n + q].powi(2);
+            }
+        }
+        if off <= f64::EPSILON.powi(2) * total {
+            break;
+        }
+
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let apq = a[p * n + q];
+                if apq == 0. {
+                    continue;
+                }
+                let theta = (a[q * n + q] - a[p * n + p]) / (2. * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+                let c = 1. / (t * t + 1.).sqrt();
+                let s = t * c;
+
+                // A <- P^T A P and V <- V P, where P is the rotation in the (p, q) plane
+                for k in 0..n {
+                    let (akp, akq) = (a[k * n + p], a[k * n + q]);
+                    a[k * n + p] = c * akp - s * akq;
+                    a[k * n + q] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[p * n + k], a[q * n + k]);
+                    a[p * n + k] = c * apk - s * aqk;
+                    a[q * n + k] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[k * n + p], v[k * n + q]);
+                    v[k * n + p] = c * vkp - s * vkq;
+                    v[k * n + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| a[i * n + i].partial_cmp(&a[j * n + j]).unwrap());
+
+    let eigenvalues = order.iter().map(|&i| a[i * n + i]).collect();
+    let mut eigenvectors = vec![0.; n * n];
+    for (col, &i) in order.iter().enumerate() {
+        for k in 0..n {
+            eigenvectors[k * n + col] = v[k * n + i];
+        }
+    }
+
+    (eigenvalues, eigenvectors)
+}
+
+/// Computes the square root of the symmetric positive semi-definite matrix `a`. That is, the
+/// symmetric positive semi-definite matrix `s` such that `s * s = a`. The matrix is decomposed with
+/// [eigen_symmetric](crate::linalg::eigen_symmetric), the square roots of the eigenvalues are
+/// taken, and the matrix is recomposed.
+///
+/// # Remarks
+/// Eigenvalues which are negative only due to rounding error are treated as zero.
+pub fn sqrtm_symmetric(a: &[f64]) -> Vec<f64> {
+    let (eigenvalues, v) = eigen_symmetric(a);
+    let n = eigenvalues.len();
+
+    let scale = eigenvalues.iter().fold(0., |acc: f64, x| acc.max(x.abs()));
+    assert!(
+        eigenvalues.iter().all(|&x| x >= -1e-10 * scale),
+        "matrix not positive semi-definite"
+    );
+    let sqrt_eig = eigenvalues
+        .iter()
+        .map(|x| x.max(0.).sqrt())
+        .collect::<Vec<_>>();
+
+    let mut s = vec![0.; n * n];
+    for i in 0..n {
+        for j in 0..n {
+            s[i * n + j] = (0..n)
+                .map(|k| v[i * n + k] * sqrt_eig[k] * v[j * n + k])
+                .sum();
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::matmul;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_eigen_symmetric() {
+        let (eigenvalues, v) = eigen_symmetric(&[2., 1., 1., 2.]);
+        assert_approx_eq!(eigenvalues[0], 1.);
+        assert_approx_eq!(eigenvalues[1], 3.);
+        assert_approx_eq!(v[0].abs(), 0.5_f64.sqrt());
+
+        let a = [
+            4., 1., -2., 2., 1., 2., 0., 1., -2., 0., 3., -2., 2., 1., -2., -1.,
+        ];
+        let n = 4;
+        let (eigenvalues, v) = eigen_symmetric(&a);
+        assert!(eigenvalues.windows(2).all(|w| w[0] <= w[1]));
+        assert_approx_eq!(eigenvalues.iter().sum::<f64>(), 8.);
+
+        let av = matmul(&a, &v, n, n, false, false);
+        let vtv = matmul(&v, &v, n, n, true, false);
+        for i in 0..n {
+            for j in 0..n {
+                // A v_j = lambda_j v_j
+                assert!((av[i * n + j] - eigenvalues[j] * v[i * n + j]).abs() < 1e-10);
+                let expected = if i == j { 1. } else { 0. };
+                assert!((vtv[i * n + j] - expected).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrtm_symmetric() {
+        let mats = vec![
+            vec![4., 0., 0., 9.],
+            vec![2., 1., 1., 2.],
+            vec![
+                6., 3., 4., 8., 3., 6., 5., 1., 4., 5., 10., 7., 8., 1., 7., 25.,
+            ],
+        ];
+        for a in mats {
+            let n = is_square(&a).unwrap();
+            let s = sqrtm_symmetric(&a);
+            for i in 0..n {
+                for j in 0..n {
+                    assert!((s[i * n + j] - s[j * n + i]).abs() < 1e-12);
+                }
+            }
+            let ss = matmul(&s, &s, n, n, false, false);
+            for i in 0..a.len() {
+                assert!((ss[i] - a[i]).abs() < 1e-10);
+            }
+        }
+        assert_eq!(sqrtm_symmetric(&[4., 0., 0., 9.]), vec![2., 0., 0., 3.]);
+    }
+
+    #[test]
+    #[should_panic(expected = "matrix not positive semi-definite")]
+    fn test_sqrtm_indefinite() {
+        sqrtm_symmetric(&[1., 2., 2., 1.]);
+    }
+}
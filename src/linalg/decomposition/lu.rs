@@ -5,7 +5,7 @@ use std::cmp;
 // #[cfg(feature = "lapack")]
 // use lapack::dgetrf;
 
-use crate::linalg::is_square;
+use crate::linalg::{ipiv_parity, is_square};
 
 /// Computes the pivoted LU decomposition of a square matrix. For some matrix A, this decomposition
 /// is A = PLU. The resulting matrix has U in its upper triangle and L in its lower triangle.
@@ -78,6 +78,27 @@ pub fn lu_solve(lu: &[f64], pivots: &[i32], b: &[f64]) -> Vec<f64> {
     x
 }
 
+/// Computes the sign and the natural logarithm of the absolute value of the determinant of a square
+/// matrix, using its LU decomposition. This avoids the overflow or underflow which can occur when
+/// computing the determinant directly for large matrices. The determinant is `sign * exp(logabsdet)`.
+/// For a singular matrix, this returns `(0, -inf)`.
+pub fn slogdet(a: &[f64]) -> (f64, f64) {
+    let n = is_square(a).unwrap();
+    let (lu, pivots) = lu(a);
+
+    let mut sign = ipiv_parity(&pivots) as f64;
+    let mut logabsdet = 0.;
+    for i in 0..n {
+        let u = lu[i * n + i];
+        if u == 0. {
+            return (0., f64::NEG_INFINITY);
+        }
+        sign *= u.signum();
+        logabsdet += u.abs().ln();
+    }
+    (sign, logabsdet)
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::lu_solve;
@@ -137,4 +158,51 @@ mod tests {
             assert_approx_eq!(x[i], x_ref[i]);
         }
     }
+
+    #[test]
+    fn test_slogdet() {
+        use crate::linalg::Matrix;
+
+        let mats = vec![
+            vec![2., 1., 1., 3.],
+            vec![0., 1., 1., 0.],
+            vec![4., -2., 1., 3., 6., -4., 2., 1., 8.],
+            vec![
+                -0.46519316,
+                -3.1042875,
+                -5.01766541,
+                -1.86300107,
+                2.7692825,
+                2.3097699,
+                -12.3854289,
+                -8.70520295,
+                6.02201052,
+                -6.71212792,
+                -1.74683781,
+                -6.08893455,
+                -2.53731118,
+                2.72112893,
+                4.70204472,
+                -1.03387848,
+            ],
+        ];
+        for a in mats {
+            let n = is_square(&a).unwrap();
+            let det = Matrix::new(a.clone(), n as i32, n as i32).det();
+            let (sign, logabsdet) = slogdet(&a);
+            assert_approx_eq!(sign * logabsdet.exp(), det);
+        }
+
+        assert_eq!(slogdet(&[1., 2., 2., 4.]), (0., f64::NEG_INFINITY));
+
+        // determinant of 0.1 * I_500 underflows, but its log does not
+        let n = 500;
+        let mut a = vec![0.; n * n];
+        for i in 0..n {
+            a[i * n + i] = 0.1;
+        }
+        let (sign, logabsdet) = slogdet(&a);
+        assert_eq!(sign, 1.);
+        assert_approx_eq!(logabsdet, n as f64 * 0.1_f64.ln());
+    }
 }
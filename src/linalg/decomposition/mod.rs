@@ -1,7 +1,11 @@
 pub mod cholesky;
+pub mod eigen;
 pub mod lu;
+pub mod qr;
 pub mod substitution;
 
 pub use cholesky::*;
+pub use eigen::*;
 pub use lu::*;
+pub use qr::*;
 pub use substitution::*;
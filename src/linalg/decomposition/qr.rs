@@ -0,0 +1,142 @@
+//! Implements [QR decomposition](https://en.wikipedia.org/wiki/QR_decomposition).
+
+use crate::linalg::{givens_rotation, norm, transpose};
+
+/// Computes the QR decomposition of the `nrows x ncols` matrix `a` using Householder reflections.
+/// Returns `(Q, R)`, where `Q` is an `nrows x nrows` orthogonal matrix and `R` is an
+/// `nrows x ncols` upper triangular matrix such that `a = QR`.
+pub fn qr_householder(a: &[f64], nrows: usize, ncols: usize) -> (Vec<f64>, Vec<f64>) {
+    assert_eq!(a.len(), nrows * ncols, "matrix shape mismatch");
+    let m = nrows;
+    let n = ncols;
+
+    let mut r = a.to_vec();
+    // accumulate Q^T by applying the same reflections to the identity
+    let mut qt = vec![0.; m * m];
+    for i in 0..m {
+        qt[i * m + i] = 1.;
+    }
+
+    for k in 0..n.min(m.saturating_sub(1)) {
+        let mut v = (k..m).map(|i| r[i * n + k]).collect::<Vec<_>>();
+        let alpha = -v[0].signum() * norm(&v);
+        v[0] -= alpha;
+        let vnorm = norm(&v);
+        if vnorm == 0. {
+            continue;
+        }
+        v.iter_mut().for_each(|x| *x /= vnorm);
+
+        // apply H = I - 2vv^T to the trailing rows of R and Q^T
+        for (mat, width) in [(&mut r, n), (&mut qt, m)] {
+            for j in 0..width {
+                let s = (k..m).map(|i| v[i - k] * mat[i * width + j]).sum::<f64>();
+                for i in k..m {
+                    mat[i * width + j] -= 2. * s * v[i - k];
+                }
+            }
+        }
+    }
+
+    (transpose(&qt, m), r)
+}
+
+/// Computes the QR decomposition of the `nrows x ncols` matrix `a` by zeroing the subdiagonal
+/// entries one at a time with [Givens rotations](crate::linalg::givens_rotation). Returns
+/// `(Q, R)`, where `Q` is an `nrows x nrows` orthogonal matrix and `R` is an `nrows x ncols` upper
+/// triangular matrix such that `a = QR`.
+pub fn qr_givens(a: &[f64], nrows: usize, ncols: usize) -> (Vec<f64>, Vec<f64>) {
+    assert_eq!(a.len(), nrows * ncols, "matrix shape mismatch");
+    let m = nrows;
+    let n = ncols;
+
+    let mut r = a.to_vec();
+    let mut qt = vec![0.; m * m];
+    for i in 0..m {
+        qt[i * m + i] = 1.;
+    }
+
+    for j in 0..n {
+        for i in (j + 1..m).rev() {
+            let (c, s) = givens_rotation(r[(i - 1) * n + j], r[i * n + j]);
+            if s == 0. {
+                continue;
+            }
+            // rotate rows i - 1 and i of R and Q^T
+            for (mat, width) in [(&mut r, n), (&mut qt, m)] {
+                for k in 0..width {
+                    let x = mat[(i - 1) * width + k];
+                    let y = mat[i * width + k];
+                    mat[(i - 1) * width + k] = c * x + s * y;
+                    mat[i * width + k] = -s * x + c * y;
+                }
+            }
+            r[i * n + j] = 0.;
+        }
+    }
+
+    (transpose(&qt, m), r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::matmul;
+
+    const A: [f64; 12] = [2., -1., 0.5, 0., 3., 1., 1., 1., -2., 4., 0.5, 2.];
+
+    fn check_qr(a: &[f64], q: &[f64], r: &[f64], m: usize, n: usize) {
+        // Q is orthogonal
+        let qtq = matmul(q, q, m, m, true, false);
+        for i in 0..m {
+            for j in 0..m {
+                let expected = if i == j { 1. } else { 0. };
+                assert!((qtq[i * m + j] - expected).abs() < 1e-12);
+            }
+        }
+        // R is upper triangular
+        for i in 0..m {
+            for j in 0..i.min(n) {
+                assert!(r[i * n + j].abs() < 1e-12);
+            }
+        }
+        // QR reconstructs A
+        let qr = matmul(q, r, m, m, false, false);
+        for i in 0..a.len() {
+            assert!((qr[i] - a[i]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_qr_householder() {
+        let (q, r) = qr_householder(&A, 4, 3);
+        check_qr(&A, &q, &r, 4, 3);
+        let (q, r) = qr_householder(&A, 3, 4);
+        check_qr(&A, &q, &r, 3, 4);
+    }
+
+    #[test]
+    fn test_qr_givens() {
+        let (q, r) = qr_givens(&A, 4, 3);
+        check_qr(&A, &q, &r, 4, 3);
+        let (q, r) = qr_givens(&A, 3, 4);
+        check_qr(&A, &q, &r, 3, 4);
+    }
+
+    #[test]
+    fn test_qr_givens_matches_householder() {
+        let (m, n) = (4, 3);
+        let (qg, rg) = qr_givens(&A, m, n);
+        let (qh, rh) = qr_householder(&A, m, n);
+        // the decomposition is unique up to the signs of the rows of R (and columns of Q)
+        for i in 0..n {
+            let sign = (rg[i * n + i] * rh[i * n + i]).signum();
+            for j in 0..n {
+                assert!((rg[i * n + j] - sign * rh[i * n + j]).abs() < 1e-12);
+            }
+            for k in 0..m {
+                assert!((qg[k * m + i] - sign * qh[k * m + i]).abs() < 1e-12);
+            }
+        }
+    }
+}
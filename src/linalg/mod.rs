@@ -3,9 +3,11 @@
 mod array;
 mod decomposition;
 mod rotations;
+mod spline;
 mod utils;
 
 pub use array::*;
 pub use decomposition::*;
 pub use rotations::*;
+pub use spline::*;
 pub use utils::*;
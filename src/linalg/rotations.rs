@@ -1,4 +1,4 @@
-/// Utilities for 3D rotations.
+/// Utilities for 3D rotations and Givens rotations.
 use super::Matrix;
 
 pub enum Axis {
@@ -96,3 +96,16 @@ pub fn rotation_matrix_ccw(angle: f64, axis: Axis) -> Matrix {
     };
     Matrix::new(data, 3, 3)
 }
+
+/// Computes the cosine and sine `(c, s)` of the [Givens
+/// rotation](https://en.wikipedia.org/wiki/Givens_rotation) which rotates the vector `(a, b)` onto
+/// the first axis. That is, `c * a + s * b = r` and `-s * a + c * b = 0`, where `r` is the length
+/// of `(a, b)`.
+pub fn givens_rotation(a: f64, b: f64) -> (f64, f64) {
+    if b == 0. {
+        (1., 0.)
+    } else {
+        let r = a.hypot(b);
+        (a / r, b / r)
+    }
+}
@@ -0,0 +1,136 @@
+//! Cubic spline interpolation.
+
+use crate::linalg::solve_tridiagonal;
+
+/// A [cubic spline](https://en.wikipedia.org/wiki/Spline_interpolation) interpolating a set of
+/// knots `(x, y)`. Between each pair of adjacent knots the spline is a cubic polynomial, and the
+/// pieces join with continuous first and second derivatives.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubicSpline {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    /// Second derivatives of the spline at the knots.
+    m: Vec<f64>,
+}
+
+impl CubicSpline {
+    /// Create a new natural cubic spline through the knots `(x, y)`, where `x` is strictly
+    /// increasing. A natural spline has a second derivative of zero at both ends.
+    ///
+    /// # Errors
+    /// Panics if `x` and `y` have different lengths, if there are fewer than two knots, or if `x`
+    /// is not strictly increasing.
+    pub fn new(x: &[f64], y: &[f64]) -> Self {
+        Self::build(x, y, None)
+    }
+
+    /// Create a new clamped cubic spline through the knots `(x, y)`, where `x` is strictly
+    /// increasing, and the first derivative of the spline is `d_start` at the first knot and
+    /// `d_end` at the last knot. If the data come from a cubic polynomial and the end derivatives
+    /// are exact, the spline reproduces the polynomial exactly.
+    ///
+    /// # Errors
+    /// Panics if `x` and `y` have different lengths, if there are fewer than two knots, or if `x`
+    /// is not strictly increasing.
+    pub fn clamped(x: &[f64], y: &[f64], d_start: f64, d_end: f64) -> Self {
+        Self::build(x, y, Some((d_start, d_end)))
+    }
+
+    fn build(x: &[f64], y: &[f64], end_slopes: Option<(f64, f64)>) -> Self {
+        assert_eq!(x.len(), y.len(), "x and y must have the same length");
+        assert!(x.len() >= 2, "need at least two knots");
+        assert!(
+            x.windows(2).all(|w| w[0] < w[1]),
+            "x must be strictly increasing"
+        );
+        let n = x.len();
+        let h = x.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>();
+        let slopes = (0..n - 1)
+            .map(|i| (y[i + 1] - y[i]) / h[i])
+            .collect::<Vec<_>>();
+
+        // tridiagonal system for the second derivatives
+        let mut lower = vec![0.; n];
+        let mut diag = vec![0.; n];
+        let mut upper = vec![0.; n];
+        let mut rhs = vec![0.; n];
+        for i in 1..n - 1 {
+            lower[i] = h[i - 1];
+            diag[i] = 2. * (h[i - 1] + h[i]);
+            upper[i] = h[i];
+            rhs[i] = 6. * (slopes[i] - slopes[i - 1]);
+        }
+        match end_slopes {
+            None => {
+                diag[0] = 1.;
+                diag[n - 1] = 1.;
+            }
+            Some((d_start, d_end)) => {
+                diag[0] = 2. * h[0];
+                upper[0] = h[0];
+                rhs[0] = 6. * (slopes[0] - d_start);
+                lower[n - 1] = h[n - 2];
+                diag[n - 1] = 2. * h[n - 2];
+                rhs[n - 1] = 6. * (d_end - slopes[n - 2]);
+            }
+        }
+
+        Self {
+            x: x.to_vec(),
+            y: y.to_vec(),
+            m: solve_tridiagonal(&lower[1..], &diag, &upper[..n - 1], &rhs),
+        }
+    }
+
+    /// Evaluate the spline at `x`. Points outside of the range of the knots are extrapolated
+    /// using the cubic polynomial of the nearest end interval.
+    pub fn eval(&self, x: f64) -> f64 {
+        let n = self.x.len();
+        // index of the left end of the interval containing x
+        let j = self.x.partition_point(|&v| v <= x).clamp(1, n - 1) - 1;
+        let h = self.x[j + 1] - self.x[j];
+        let a = self.x[j + 1] - x;
+        let b = x - self.x[j];
+        (self.m[j] * a.powi(3) + self.m[j + 1] * b.powi(3)) / (6. * h)
+            + (self.y[j] / h - self.m[j] * h / 6.) * a
+            + (self.y[j + 1] / h - self.m[j + 1] * h / 6.) * b
+    }
+
+    /// Evaluate the spline at each point in `x`.
+    pub fn eval_vec(&self, x: &[f64]) -> Vec<f64> {
+        x.iter().map(|&xi| self.eval(xi)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_cubic_spline() {
+        let f = |x: f64| 0.5 * x.powi(3) - 2. * x.powi(2) + x - 3.;
+        let df = |x: f64| 1.5 * x.powi(2) - 4. * x + 1.;
+        let x = [-2., -1.2, 0., 0.5, 1.5, 3., 4.];
+        let y = x.iter().map(|&xi| f(xi)).collect::<Vec<_>>();
+
+        let natural = CubicSpline::new(&x, &y);
+        for (xi, yi) in x.iter().zip(&y) {
+            assert_approx_eq!(natural.eval(*xi), *yi);
+        }
+        // the natural spline has zero curvature at the ends, so it only approximates the cubic
+        assert!((natural.eval(1.) - f(1.)).abs() < 0.1);
+
+        let clamped = CubicSpline::clamped(&x, &y, df(-2.), df(4.));
+        let xnew = [-2.5, -1.7, -0.3, 0.25, 1., 2.2, 3.9, 5.];
+        let ynew = clamped.eval_vec(&xnew);
+        for (xi, yi) in xnew.iter().zip(&ynew) {
+            assert_approx_eq!(*yi, f(*xi));
+        }
+
+        // two knots give a straight line
+        let line = CubicSpline::new(&[0., 2.], &[1., 5.]);
+        assert_eq!(line.eval_vec(&[0., 0.5, 2.]), vec![1., 2., 5.]);
+    }
+}
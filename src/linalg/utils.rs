@@ -218,6 +218,75 @@ pub fn solve_sys(a: &[f64], b: &[f64]) -> Vec<f64> {
     let n = is_square(a).unwrap();
     let nsys = is_matrix(b, n).unwrap();
 
+    #[cfg(feature = "lapack")]
+    {
+        solve_multiple(a, b, n, nsys)
+    }
+
+    #[cfg(not(feature = "lapack"))]
+    {
+        if is_positive_definite(a) {
+            let mut solutions = Vec::with_capacity(b.len());
+            let b = row_to_col_major(b, n);
+            let l = cholesky(a);
+            for i in 0..nsys {
+                let sol = cholesky_solve(&l, &b[(i * n)..((i + 1) * n)]);
+                assert_eq!(sol.len(), n);
+                solutions.extend_from_slice(&sol);
+            }
+            col_to_row_major(&solutions, n)
+        } else {
+            solve_multiple(a, b, n, nsys)
+        }
+    }
+}
+
+/// Solves the tridiagonal linear system `Ax = rhs` in `O(n)` time using the [Thomas
+/// algorithm](https://en.wikipedia.org/wiki/Tridiagonal_matrix_algorithm), where `diag` is the
+/// main diagonal of `A` (of length `n`), and `lower` and `upper` are the diagonals below and above
+/// it (of length `n - 1`). The algorithm does not pivot, so it is only guaranteed to be stable
+/// when `A` is diagonally dominant or symmetric positive definite.
+///
+/// # Errors
+/// Panics if the diagonals and `rhs` do not have compatible lengths.
+pub fn solve_tridiagonal(lower: &[f64], diag: &[f64], upper: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let n = diag.len();
+    assert!(n > 0, "matrix must not be empty");
+    assert!(
+        lower.len() == n - 1 && upper.len() == n - 1,
+        "off-diagonals must have length one less than the diagonal"
+    );
+    assert_eq!(rhs.len(), n, "rhs must have the same length as diag");
+
+    // forward sweep, eliminating the lower diagonal
+    let mut c = vec![0.; n];
+    let mut d = vec![0.; n];
+    d[0] = rhs[0] / diag[0];
+    if n > 1 {
+        c[0] = upper[0] / diag[0];
+    }
+    for i in 1..n {
+        let denom = diag[i] - lower[i - 1] * c[i - 1];
+        if i < n - 1 {
+            c[i] = upper[i] / denom;
+        }
+        d[i] = (rhs[i] - lower[i - 1] * d[i - 1]) / denom;
+    }
+    // back substitution
+    for i in (0..n - 1).rev() {
+        d[i] -= c[i] * d[i + 1];
+    }
+    d
+}
+
+/// Solves the linear system `AX = B` for the `nrows x nrhs` matrix `X`, where `a` is an `nrows x
+/// nrows` matrix and `b` is an `nrows x nrhs` matrix of right-hand sides (both row-major). `A` is
+/// LU factorized only once, so this is much faster than calling [solve] for each column of `B`.
+pub fn solve_multiple(a: &[f64], b: &[f64], nrows: usize, nrhs: usize) -> Vec<f64> {
+    assert_eq!(a.len(), nrows * nrows, "matrix not square");
+    assert_eq!(b.len(), nrows * nrhs, "matrix shape mismatch");
+    let n = nrows;
+
     #[cfg(feature = "lapack")]
     {
         let mut a = row_to_col_major(&a, n);
@@ -227,7 +296,7 @@ pub fn solve_sys(a: &[f64], b: &[f64]) -> Vec<f64> {
         unsafe {
             dgesv(
                 n as i32,
-                nsys as i32,
+                nrhs as i32,
                 &mut a,
                 n as i32,
                 &mut ipiv,
@@ -244,23 +313,12 @@ pub fn solve_sys(a: &[f64], b: &[f64]) -> Vec<f64> {
     {
         let mut solutions = Vec::with_capacity(b.len());
         let b = row_to_col_major(b, n);
-
-        if is_positive_definite(a) {
-            let l = cholesky(a);
-            for i in 0..nsys {
-                let sol = cholesky_solve(&l, &b[(i * n)..((i + 1) * n)]);
-                assert_eq!(sol.len(), n);
-                solutions.extend_from_slice(&sol);
-            }
-        } else {
-            let (lu, piv) = lu(a);
-            for i in 0..nsys {
-                let sol = lu_solve(&lu, &piv, &b[(i * n)..((i + 1) * n)]);
-                assert_eq!(sol.len(), n);
-                solutions.extend_from_slice(&sol);
-            }
+        let (lu, piv) = lu(a);
+        for i in 0..nrhs {
+            let sol = lu_solve(&lu, &piv, &b[(i * n)..((i + 1) * n)]);
+            assert_eq!(sol.len(), n);
+            solutions.extend_from_slice(&sol);
         }
-
         col_to_row_major(&solutions, n)
     }
 }
@@ -304,6 +362,9 @@ pub fn solve(a: &[f64], b: &[f64]) -> Vec<f64> {
     }
 }
 
+/// Default block size used for loop tiling in [matmul](crate::linalg::matmul).
+pub const MATMUL_BLOCK_SIZE: usize = 64;
+
 /// Performs blocked matrix multiplication with block size `bsize`. See the API for the
 /// [matmul](crate::linalg::matmul).
 pub fn matmul_blocked(
@@ -315,6 +376,8 @@ pub fn matmul_blocked(
     transpose_b: bool,
     bsize: usize,
 ) -> Vec<f64> {
+    assert!(bsize > 0, "block size must be positive");
+
     let cols_a = is_matrix(a, rows_a).unwrap();
     let cols_b = is_matrix(b, rows_b).unwrap();
 
@@ -322,8 +385,6 @@ pub fn matmul_blocked(
     let l = if transpose_a { rows_a } else { cols_a };
     let n = if transpose_b { rows_b } else { cols_b };
 
-    let mut c = vec![0.; m * n];
-
     let a = if transpose_a {
         transpose(a, rows_a)
     } else {
@@ -335,24 +396,19 @@ pub fn matmul_blocked(
         b.to_vec()
     };
 
-    // https://courses.engr.illinois.edu/cs232/sp2009/lectures/X18.pdf
-    for jj in 0..(n / bsize + 1) {
-        for kk in 0..(l / bsize + 1) {
-            for i in 0..m {
-                for k in (kk * bsize)..std::cmp::min((kk * bsize) + bsize, l) {
-                    let temp = a[i * l + k];
-                    for j in (jj * bsize)..std::cmp::min((jj * bsize) + bsize, n) {
-                        c[i * n + j] += temp * b[k * n + j];
-                    }
-                }
-            }
+    #[cfg(feature = "rayon")]
+    {
+        if m * l * n >= PAR_MATMUL_THRESHOLD {
+            return matmul_par(&a, &b, m, l, n, bsize);
         }
     }
 
-    c
+    matmul_serial(&a, &b, m, l, n, bsize)
 }
 
-/// Multiply two matrices together, optionally transposing one or both of them.
+/// Multiply two matrices together, optionally transposing one or both of them. Without BLAS, the
+/// loops are tiled with block size [MATMUL_BLOCK_SIZE](crate::linalg::MATMUL_BLOCK_SIZE) (see
+/// [matmul_blocked](crate::linalg::matmul_blocked)).
 pub fn matmul(
     a: &[f64],
     b: &[f64],
@@ -361,11 +417,11 @@ pub fn matmul(
     transpose_a: bool,
     transpose_b: bool,
 ) -> Vec<f64> {
-    let cols_a = is_matrix(a, rows_a).unwrap();
-    let cols_b = is_matrix(b, rows_b).unwrap();
-
     #[cfg(feature = "blas")]
     {
+        let cols_a = is_matrix(a, rows_a).unwrap();
+        let cols_b = is_matrix(b, rows_b).unwrap();
+
         // some swapping to use row-major ordering
         // uses the identity AB = (B^T A^T)^T
         // with B^T and A^T being passed as transpose flags to BLAS
@@ -420,39 +476,76 @@ pub fn matmul(
 
     #[cfg(not(feature = "blas"))]
     {
-        // if transposing both, use the identity A^T.B^T = (A.B)^T
-        if transpose_a && transpose_b {
-            return transpose(&matmul(a, b, rows_a, rows_b, false, false), cols_a);
-        }
+        matmul_blocked(
+            a,
+            b,
+            rows_a,
+            rows_b,
+            transpose_a,
+            transpose_b,
+            MATMUL_BLOCK_SIZE,
+        )
+    }
+}
 
-        let m = if transpose_a { cols_a } else { rows_a };
-        let l = if transpose_a { rows_a } else { cols_a };
-        let n = if transpose_b { rows_b } else { cols_b };
+/// Minimum number of multiply-adds (`m * l * n`) before matrix multiplication splits its outer
+/// loop across threads.
+#[cfg(feature = "rayon")]
+const PAR_MATMUL_THRESHOLD: usize = 1 << 18;
 
-        let mut c = vec![0.; m * n];
+/// Accumulates the product of a strip of rows `a_rows` of an m x l matrix and an l x n matrix `b`
+/// into the corresponding strip of rows `c_rows` of the result, tiling the `k` and `j` loops with
+/// block size `bsize`. Each element of the result accumulates its terms in increasing `k`, so the
+/// result is the same for every block size.
+fn matmul_strip(a_rows: &[f64], b: &[f64], c_rows: &mut [f64], l: usize, n: usize, bsize: usize) {
+    let rows = c_rows.len() / n;
 
-        let a = if transpose_a {
-            transpose(a, rows_a)
-        } else {
-            a.to_vec()
-        };
-        let b = if transpose_b {
-            transpose(b, rows_b)
-        } else {
-            b.to_vec()
-        };
-
-        for i in 0..m {
-            for k in 0..l {
-                let temp = a[i * l + k];
-                for j in 0..n {
-                    c[i * n + j] += temp * b[k * n + j];
+    // https://courses.engr.illinois.edu/cs232/sp2009/lectures/X18.pdf
+    for kk in (0..l).step_by(bsize) {
+        let kend = std::cmp::min(kk + bsize, l);
+        for jj in (0..n).step_by(bsize) {
+            let jend = std::cmp::min(jj + bsize, n);
+            for i in 0..rows {
+                let c_row = &mut c_rows[(i * n + jj)..(i * n + jend)];
+                for k in kk..kend {
+                    let temp = a_rows[i * l + k];
+                    let b_row = &b[(k * n + jj)..(k * n + jend)];
+                    for (cij, bkj) in c_row.iter_mut().zip(b_row) {
+                        *cij += temp * bkj;
+                    }
                 }
             }
         }
+    }
+}
 
-        c
+/// Multiplies an m x l matrix `a` and an l x n matrix `b`, both untransposed, on one thread.
+fn matmul_serial(a: &[f64], b: &[f64], m: usize, l: usize, n: usize, bsize: usize) -> Vec<f64> {
+    let mut c = vec![0.; m * n];
+    if m * l * n == 0 {
+        return c;
+    }
+    for (a_rows, c_rows) in a.chunks(l * bsize).zip(c.chunks_mut(n * bsize)) {
+        matmul_strip(a_rows, b, c_rows, l, n, bsize);
+    }
+    c
+}
+
+/// Multiplies an m x l matrix `a` and an l x n matrix `b`, both untransposed, computing strips of
+/// `bsize` rows of the result in parallel. Each row is accumulated in the same order as in
+/// `matmul_serial`, so the result is identical.
+#[cfg(feature = "rayon")]
+fn matmul_par(a: &[f64], b: &[f64], m: usize, l: usize, n: usize, bsize: usize) -> Vec<f64> {
+    use rayon::prelude::*;
+
+    let mut c = vec![0.; m * n];
+    if m * l * n == 0 {
+        return c;
     }
+    c.par_chunks_mut(n * bsize)
+        .zip(a.par_chunks(l * bsize))
+        .for_each(|(c_rows, a_rows)| matmul_strip(a_rows, b, c_rows, l, n, bsize));
+    c
 }
 
 /// Create a design matrix from a given matrix.
@@ -602,6 +695,110 @@ pub fn norm(x: &[f64]) -> f64 {
     dot(x, x).sqrt()
 }
 
+/// Calculates the [cosine similarity](https://en.wikipedia.org/wiki/Cosine_similarity) of two
+/// equal-length vectors, which is their dot product divided by the product of their norms. The
+/// result is clamped to `[-1, 1]` to guard against rounding error. Returns NaN if either vector is
+/// zero.
+pub fn cosine_similarity(a: &[f64], b: &[f64]) -> f64 {
+    (dot(a, b) / (norm(a) * norm(b))).clamp(-1., 1.)
+}
+
+/// Calculates the Euclidean distance between two equal-length vectors.
+pub fn euclidean_distance(a: &[f64], b: &[f64]) -> f64 {
+    assert_eq!(a.len(), b.len(), "vectors must have the same length");
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Clamps each element of a vector into the range `[min, max]`.
+///
+/// # Errors
+/// Panics if `min > max`.
+pub fn clip(data: &[f64], min: f64, max: f64) -> Vec<f64> {
+    assert!(min <= max, "min must be less than or equal to max");
+    data.iter().map(|x| x.clamp(min, max)).collect()
+}
+
+/// How to handle points outside of the range of the data when interpolating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extrapolation {
+    /// Use the value at the nearest endpoint.
+    Clamp,
+    /// Return NaN.
+    NaN,
+}
+
+/// Linearly interpolates the points `(x, y)` at the points `xnew`, where `x` is sorted in
+/// ascending order. Points outside of the range of `x` take the value at the nearest endpoint. See
+/// [interp1d_with] to configure this.
+///
+/// # Errors
+/// Panics if `x` and `y` have different lengths or have fewer than two points.
+pub fn interp1d(x: &[f64], y: &[f64], xnew: &[f64]) -> Vec<f64> {
+    interp1d_with(x, y, xnew, Extrapolation::Clamp)
+}
+
+/// Linearly interpolates the points `(x, y)` at the points `xnew`, where `x` is sorted in
+/// ascending order. Points outside of the range of `x` are handled according to `extrapolation`.
+///
+/// # Errors
+/// Panics if `x` and `y` have different lengths or have fewer than two points.
+pub fn interp1d_with(x: &[f64], y: &[f64], xnew: &[f64], extrapolation: Extrapolation) -> Vec<f64> {
+    assert_eq!(x.len(), y.len(), "x and y must have the same length");
+    assert!(x.len() >= 2, "need at least two points to interpolate");
+    let n = x.len();
+
+    xnew.iter()
+        .map(|&xi| {
+            if xi < x[0] || xi > x[n - 1] {
+                return match extrapolation {
+                    Extrapolation::Clamp if xi < x[0] => y[0],
+                    Extrapolation::Clamp => y[n - 1],
+                    Extrapolation::NaN => f64::NAN,
+                };
+            }
+            if xi.is_nan() {
+                return f64::NAN;
+            }
+            // index of the right end of the interval containing xi
+            let j = x.partition_point(|&v| v < xi).clamp(1, n - 1);
+            let t = (xi - x[j - 1]) / (x[j] - x[j - 1]);
+            y[j - 1] + t * (y[j] - y[j - 1])
+        })
+        .collect()
+}
+
+/// Returns the index of the bin that each value in `data` falls into, given the bin `edges`, with
+/// the same semantics as numpy's `digitize`. If the edges are increasing, the index `i` of a value
+/// `x` satisfies `edges[i - 1] <= x < edges[i]`; if they are decreasing, it satisfies
+/// `edges[i - 1] > x >= edges[i]`. Values before the first edge get index 0 and values past the
+/// last edge get index `edges.len()`. As in numpy, NaN values are treated as larger than every
+/// edge.
+///
+/// # Errors
+/// Panics if `edges` is empty or is not monotonic.
+pub fn digitize(data: &[f64], edges: &[f64]) -> Vec<usize> {
+    assert!(!edges.is_empty(), "edges must not be empty");
+    let increasing = edges.windows(2).all(|w| w[0] <= w[1]);
+    let decreasing = edges.windows(2).all(|w| w[0] >= w[1]);
+    assert!(increasing || decreasing, "edges must be monotonic");
+
+    data.iter()
+        .map(|&x| {
+            if increasing {
+                // the number of edges that are less than or equal to x
+                edges.partition_point(|&e| e <= x || x.is_nan())
+            } else {
+                // the number of edges that are greater than x
+                edges.partition_point(|&e| e > x)
+            }
+        })
+        .collect()
+}
+
 /// Calculates the infinity norm of a matrix. That is, it sums the absolute values along each row,
 /// and then returns the largest of these values.
 pub fn inf_norm(x: &[f64], nrows: usize) -> f64 {
@@ -668,6 +865,122 @@ mod tests {
         }
     }
 
+    #[cfg(all(feature = "rayon", not(feature = "blas")))]
+    #[test]
+    fn test_matmul_par() {
+        let (m, l, n) = (97, 83, 71);
+        let a = (0..m * l)
+            .map(|i| ((i * 37 % 101) as f64 - 50.) / 7.)
+            .collect::<Vec<_>>();
+        let b = (0..l * n)
+            .map(|i| ((i * 53 % 89) as f64 - 44.) / 3.)
+            .collect::<Vec<_>>();
+
+        let serial = matmul_serial(&a, &b, m, l, n, MATMUL_BLOCK_SIZE);
+        let par = matmul_par(&a, &b, m, l, n, MATMUL_BLOCK_SIZE);
+        assert_eq!(
+            serial.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+            par.iter().map(|x| x.to_bits()).collect::<Vec<_>>()
+        );
+        assert_eq!(matmul(&a, &b, m, l, false, false), serial);
+    }
+
+    #[cfg(not(feature = "blas"))]
+    #[test]
+    fn test_matmul_transpose_combinations() {
+        // reference triple loop
+        fn naive(a: &[f64], b: &[f64], m: usize, l: usize, n: usize) -> Vec<f64> {
+            let mut c = vec![0.; m * n];
+            for i in 0..m {
+                for k in 0..l {
+                    for j in 0..n {
+                        c[i * n + j] += a[i * l + k] * b[k * n + j];
+                    }
+                }
+            }
+            c
+        }
+
+        let (m, l, n) = (37, 29, 23);
+        let a = (0..m * l)
+            .map(|i| ((i * 37 % 101) as f64 - 50.) / 7.)
+            .collect::<Vec<_>>();
+        let b = (0..l * n)
+            .map(|i| ((i * 53 % 89) as f64 - 44.) / 3.)
+            .collect::<Vec<_>>();
+        let at = transpose(&a, m);
+        let bt = transpose(&b, l);
+        let expected = naive(&a, &b, m, l, n);
+
+        assert_eq!(matmul(&a, &b, m, l, false, false), expected);
+        assert_eq!(matmul(&at, &b, l, l, true, false), expected);
+        assert_eq!(matmul(&a, &bt, m, n, false, true), expected);
+        assert_eq!(matmul(&at, &bt, l, n, true, true), expected);
+
+        for bsize in [1, 4, 16, 100] {
+            assert_eq!(matmul_blocked(&a, &b, m, l, false, false, bsize), expected);
+            assert_eq!(matmul_blocked(&at, &bt, l, n, true, true, bsize), expected);
+        }
+    }
+
+    #[test]
+    fn test_similarity_distance() {
+        let a = [1., -2., 3.5];
+        assert_approx_eq!(cosine_similarity(&a, &a), 1.);
+        assert_eq!(euclidean_distance(&a, &a), 0.);
+        assert_approx_eq!(cosine_similarity(&a, &[-2., 4., -7.]), -1.);
+
+        let b = [1., 0., 0.];
+        let c = [0., 3., 0.];
+        assert_eq!(cosine_similarity(&b, &c), 0.);
+        assert_approx_eq!(euclidean_distance(&b, &c), 10_f64.sqrt());
+    }
+
+    #[test]
+    fn test_clip() {
+        let x = [-3.5, -1., 0., 0.25, 1., 2.7];
+        assert_eq!(clip(&x, -1., 1.), vec![-1., -1., 0., 0.25, 1., 1.]);
+        assert_eq!(clip(&x, 0.25, 0.25), vec![0.25; 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "min must be less than or equal to max")]
+    fn test_clip_bad_range() {
+        clip(&[1., 2.], 1., 0.);
+    }
+
+    #[test]
+    fn test_digitize() {
+        let edges = [0., 1., 2.5, 4.];
+        let x = [-1., 0., 0.5, 1., 2.5, 3.9, 4., 10., f64::NAN];
+        assert_eq!(digitize(&x, &edges), vec![0, 1, 1, 2, 3, 3, 4, 4, 4]);
+
+        let edges = [4., 2.5, 1., 0.];
+        assert_eq!(digitize(&x[..8], &edges), vec![4, 3, 3, 2, 1, 1, 0, 0]);
+
+        assert_eq!(digitize(&[0.5, 1., 1.5], &[1.]), vec![0, 1, 1]);
+        assert!(digitize(&[], &edges).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "edges must be monotonic")]
+    fn test_digitize_not_monotonic() {
+        digitize(&[1.], &[0., 2., 1.]);
+    }
+
+    #[test]
+    fn test_interp1d() {
+        let x = [0., 1., 2.5, 4.];
+        let y = [1., 3., -1.5, 0.];
+        assert_eq!(interp1d(&x, &y, &x), y.to_vec());
+        assert_eq!(interp1d(&x, &y, &[0.5, 1.75, 3.25]), vec![2., 0.75, -0.75]);
+        assert_eq!(interp1d(&x, &y, &[-1., 5.]), vec![1., 0.]);
+
+        let extrapolated = interp1d_with(&x, &y, &[-1., 0.2, 5.], Extrapolation::NaN);
+        assert!(extrapolated[0].is_nan() && extrapolated[2].is_nan());
+        assert_approx_eq!(extrapolated[1], 1.4);
+    }
+
     #[test]
     fn test_matmul() {
         let x = [
@@ -735,6 +1048,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_solve_multiple() {
+        let a = [4., -2., 1., 3., 6., -4., 2., 1., 8.];
+        let b = [12., 1., -3., -25., 0.5, 2., 32., -7., 1.];
+        let x = solve_multiple(&a, &b, 3, 3);
+        assert_eq!(x.len(), 9);
+        for j in 0..3 {
+            let col = (0..3).map(|i| b[i * 3 + j]).collect::<Vec<_>>();
+            let expected = solve(&a, &col);
+            for i in 0..3 {
+                assert_approx_eq!(x[i * 3 + j], expected[i], 1e-12);
+            }
+        }
+
+        // solving against the identity inverts the matrix
+        let inv = solve_multiple(&a, &[1., 0., 0., 0., 1., 0., 0., 0., 1.], 3, 3);
+        let expected = invert_matrix(&a);
+        for i in 0..9 {
+            assert!((inv[i] - expected[i]).abs() < 1e-12);
+        }
+
+        // a non-square right-hand side
+        let x = solve_multiple(&a, &b[..6], 3, 2);
+        assert_eq!(x.len(), 6);
+        let ax = matmul(&a, &x, 3, 3, false, false);
+        for i in 0..6 {
+            assert!((ax[i] - b[i]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_solve_tridiagonal() {
+        let lower = [1., -2., 0.5, 3.];
+        let diag = [4., 5., -6., 4., 7.];
+        let upper = [-1., 2., 1., -2.];
+        let rhs = [3., -1., 2., 8., 0.5];
+        let x = solve_tridiagonal(&lower, &diag, &upper, &rhs);
+
+        let mut dense = vec![0.; 25];
+        for i in 0..5 {
+            dense[i * 5 + i] = diag[i];
+            if i > 0 {
+                dense[i * 5 + i - 1] = lower[i - 1];
+            }
+            if i < 4 {
+                dense[i * 5 + i + 1] = upper[i];
+            }
+        }
+        let expected = solve(&dense, &rhs);
+        for i in 0..5 {
+            assert_approx_eq!(x[i], expected[i], 1e-12);
+        }
+
+        assert_eq!(solve_tridiagonal(&[], &[2.], &[], &[3.]), vec![1.5]);
+    }
+
     #[test]
     fn test_linspace() {
         let r1 = vec![2., 2.4, 2.8, 3.2, 3.6, 4.];
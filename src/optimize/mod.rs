@@ -4,10 +4,12 @@ pub mod gradient;
 // pub mod loss;
 pub mod num_gradient;
 pub mod optimizers;
+pub mod transform;
 // pub mod sim_annealing;
 
 pub use self::gradient::*;
 // pub use self::loss::*;
 // pub use self::num_gradient::*;
 pub use self::optimizers::*;
+pub use self::transform::*;
 // pub use self::sim_annealing::*;
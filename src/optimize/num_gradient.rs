@@ -42,6 +42,32 @@ where
     (f(&xph) - f(&xmh)) / (2. * h)
 }
 
+/// Calculates the Jacobian of a vector-valued function `f` at `x` using forward differences. If `f`
+/// maps `n` inputs to `m` outputs, the result is the `m x n` Jacobian matrix in row-major order,
+/// so that the element at `i * n + j` is the partial derivative of the `i`th output with respect to
+/// the `j`th input.
+pub fn jacobian<F>(f: F, x: &[f64]) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    let n = x.len();
+    let fx = f(x);
+    let m = fx.len();
+    let mut jac = vec![0.; m * n];
+    let mut xph = x.to_owned();
+    for j in 0..n {
+        let h = calc_h(x[j]);
+        xph[j] = x[j] + h;
+        let fxph = f(&xph);
+        assert_eq!(fxph.len(), m, "f must return the same number of outputs");
+        for i in 0..m {
+            jac[i * n + j] = (fxph[i] - fx[i]) / h;
+        }
+        xph[j] = x[j];
+    }
+    jac
+}
+
 /// Given a function, return its derivative (a function).
 pub fn derivative<F>(f: F) -> impl Fn(f64) -> f64 + Copy
 where
@@ -50,6 +76,31 @@ where
     move |x: f64| sym_der(f, x)
 }
 
+/// Estimates the gradient of sampled data `y = f(x)` at each of the sample points `x`, which need
+/// not be evenly spaced. Interior points use second-order accurate central differences and the two
+/// endpoints use first-order one-sided differences, matching numpy's `gradient`.
+///
+/// # Errors
+/// Panics if `x` and `y` have different lengths or have fewer than two points.
+pub fn numerical_gradient(x: &[f64], y: &[f64]) -> Vec<f64> {
+    assert_eq!(x.len(), y.len(), "x and y must have the same length");
+    let n = x.len();
+    assert!(n >= 2, "need at least two points to compute a gradient");
+
+    let mut grad = Vec::with_capacity(n);
+    grad.push((y[1] - y[0]) / (x[1] - x[0]));
+    for i in 1..n - 1 {
+        let hs = x[i] - x[i - 1];
+        let hd = x[i + 1] - x[i];
+        grad.push(
+            (hs.powi(2) * y[i + 1] + (hd.powi(2) - hs.powi(2)) * y[i] - hd.powi(2) * y[i - 1])
+                / (hs * hd * (hd + hs)),
+        );
+    }
+    grad.push((y[n - 1] - y[n - 2]) / (x[n - 1] - x[n - 2]));
+    grad
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,4 +155,38 @@ mod tests {
         // partial of a closure
         assert_approx_eq!(partial(|x: &[f64]| x[0] * x[0] + 2., &[0.], 0), 0.);
     }
+
+    #[test]
+    fn test_numerical_gradient() {
+        let x = [-2., -1.5, -0.7, 0., 0.4, 1.1, 2., 3.];
+        let y = x.iter().map(|v| v * v).collect::<Vec<_>>();
+        let grad = numerical_gradient(&x, &y);
+        assert_eq!(grad.len(), x.len());
+        for i in 1..x.len() - 1 {
+            assert_approx_eq!(grad[i], 2. * x[i]);
+        }
+        // one-sided differences are exact at the midpoint of the first and last intervals
+        assert_approx_eq!(grad[0], x[0] + x[1]);
+        assert_approx_eq!(grad[7], x[6] + x[7]);
+
+        assert_eq!(numerical_gradient(&[0., 2.], &[1., 5.]), vec![2., 2.]);
+    }
+
+    #[test]
+    #[should_panic(expected = "need at least two points")]
+    fn test_numerical_gradient_too_short() {
+        numerical_gradient(&[1.], &[1.]);
+    }
+
+    #[test]
+    fn test_jacobian() {
+        let f = |x: &[f64]| vec![x[0].powi(2), x[0] * x[1], x[1].sin()];
+        let x = [1.5, -0.5];
+        let jac = jacobian(f, &x);
+        let jac_ref = [2. * x[0], 0., x[1], x[0], 0., x[1].cos()];
+        assert_eq!(jac.len(), jac_ref.len());
+        for i in 0..jac.len() {
+            assert!((jac[i] - jac_ref[i]).abs() < 1e-6);
+        }
+    }
 }
@@ -114,6 +114,39 @@ impl Optimizer for Adam {
     }
 }
 
+/// Minimizes a function using Adam, given a function `grad` which returns the (possibly noisy)
+/// gradient of the objective at a set of parameters. Starting from `x0`, runs `max_iter` steps of
+/// size `lr` using the defaults recommended by Kingma and Ba 2014 for the other hyperparameters
+/// (`beta1 = 0.9`, `beta2 = 0.999`, `epsilon = 1e-8`).
+pub fn adam<F>(grad: F, x0: &[f64], lr: f64, max_iter: usize) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    let Adam {
+        beta1,
+        beta2,
+        epsilon,
+        ..
+    } = Adam::default();
+    let mut params = x0.to_vec();
+    let param_len = params.len();
+    let mut m = vec![0.; param_len];
+    let mut v = vec![0.; param_len];
+
+    for t in 1..=max_iter {
+        let g = grad(&params);
+        assert_eq!(g.len(), param_len, "gradient has the wrong length");
+        for p in 0..param_len {
+            m[p] = beta1 * m[p] + (1. - beta1) * g[p];
+            v[p] = beta2 * v[p] + (1. - beta2) * g[p].powi(2);
+            let mhat = m[p] / (1. - beta1.powi(t as i32));
+            let vhat = v[p] / (1. - beta2.powi(t as i32));
+            params[p] -= lr * mhat / (vhat.sqrt() + epsilon);
+        }
+    }
+    params
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,4 +186,14 @@ mod tests {
             assert_approx_eq!(est_params[i], coeffs[i], 0.015);
         }
     }
+
+    #[test]
+    fn test_adam_fn_quadratic() {
+        // f(x, y) = (x - 3)^2 + 10 (y + 1)^2 + x y
+        let grad = |p: &[f64]| vec![2. * (p[0] - 3.) + p[1], 20. * (p[1] + 1.) + p[0]];
+        let opt = adam(grad, &[0., 0.], 0.05, 5000);
+        // solution of the linear system given by setting the gradient to zero
+        assert_approx_eq!(opt[0], 140. / 39., 1e-4);
+        assert_approx_eq!(opt[1], -46. / 39., 1e-4);
+    }
 }
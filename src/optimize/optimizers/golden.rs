@@ -0,0 +1,50 @@
+/// Finds the minimum of a unimodal function `f` on the interval `[a, b]` using [golden-section
+/// search](https://en.wikipedia.org/wiki/Golden-section_search). The bracket is narrowed by the
+/// golden ratio each step until its width is below `tol`, and the midpoint of the final bracket is
+/// returned. If `f` is not unimodal on `[a, b]`, this may return a local minimum.
+pub fn golden_section<F>(f: F, a: f64, b: f64, tol: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    assert!(tol > 0., "tol must be positive");
+    let invphi = (5_f64.sqrt() - 1.) / 2.;
+    let (mut a, mut b) = if a < b { (a, b) } else { (b, a) };
+
+    let mut c = b - invphi * (b - a);
+    let mut d = a + invphi * (b - a);
+    let mut fc = f(c);
+    let mut fd = f(d);
+
+    while b - a > tol {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - invphi * (b - a);
+            fc = f(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + invphi * (b - a);
+            fd = f(d);
+        }
+    }
+
+    (a + b) / 2.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx_eq::assert_approx_eq;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_golden_section() {
+        assert_approx_eq!(golden_section(|x| (x - 3.).powi(2), 0., 10., 1e-8), 3.);
+        assert_approx_eq!(golden_section(|x| x.cos(), 0., 2. * PI, 1e-8), PI);
+        // bracket given in reverse order
+        assert_approx_eq!(golden_section(|x| (x + 1.5).abs(), 4., -6., 1e-8), -1.5);
+    }
+}
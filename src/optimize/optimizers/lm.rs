@@ -1,5 +1,6 @@
 use crate::linalg::{diag, dot, inf_norm, matmul, norm, solve, svmul, vadd, xtx};
 use crate::optimize::gradient::gradient;
+use crate::optimize::num_gradient::jacobian;
 use crate::statistics::max;
 use autodiff::F1;
 
@@ -190,3 +191,97 @@ impl Optimizer for LM {
         self.gradfn
     }
 }
+
+/// Minimizes the sum of squares of the vector of residuals returned by `residuals` using the
+/// [Levenberg-Marquardt algorithm](https://en.wikipedia.org/wiki/Levenberg%E2%80%93Marquardt_algorithm),
+/// starting from the initial parameters `x0`. Unlike [LM](crate::optimize::LM), the model does not
+/// need to be written in terms of dual numbers, since the Jacobian of the residuals is computed
+/// numerically with [jacobian](crate::optimize::num_gradient::jacobian). The damping parameter is
+/// adapted each iteration based on the ratio of the actual to the predicted reduction in error.
+///
+/// Iteration stops after `max_iter` steps, when the largest component of the gradient falls below
+/// `tol`, or when the relative change in the parameters falls below `tol`.
+pub fn levenberg_marquardt<F>(residuals: F, x0: &[f64], max_iter: usize, tol: f64) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    let mut params = x0.to_vec();
+    let param_len = params.len();
+
+    let mut res = residuals(&params);
+    let n = res.len();
+    let mut jac = jacobian(&residuals, &params);
+    let mut jtj = xtx(&jac, n);
+    let mut jtr = matmul(&jac, &res, n, n, true, false);
+
+    let mut mu = 1e-3 * max(&diag(&jtj));
+    let mut nu = 2.;
+
+    for _ in 0..max_iter {
+        if inf_norm(&jtr, param_len) <= tol {
+            break;
+        }
+
+        let mut damped = jtj.clone();
+        for i in 0..param_len {
+            damped[i * param_len + i] += mu;
+        }
+        let delta = svmul(-1., &solve(&damped, &jtr));
+
+        if norm(&delta) <= tol * (norm(&params) + tol) {
+            break;
+        }
+
+        let new_params = vadd(&params, &delta);
+        let new_res = residuals(&new_params);
+
+        // gain ratio (actual reduction in error over predicted reduction)
+        let pred_reduction = dot(&delta, &vadd(&svmul(mu, &delta), &svmul(-1., &jtr)));
+        let rho = (dot(&res, &res) - dot(&new_res, &new_res)) / pred_reduction;
+
+        if rho > 0. {
+            params = new_params;
+            res = new_res;
+            jac = jacobian(&residuals, &params);
+            jtj = xtx(&jac, n);
+            jtr = matmul(&jac, &res, n, n, true, false);
+            mu *= f64::max(1. / 3., 1. - (2. * rho - 1.).powi(3));
+            nu = 2.;
+        } else {
+            mu *= nu;
+            nu *= 2.;
+        }
+    }
+
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{Distribution1D, Normal};
+    use crate::linalg::linspace;
+
+    #[test]
+    fn test_levenberg_marquardt() {
+        alea::set_seed(42);
+        let (a, b) = (2.5, 0.4);
+        let x = linspace(0., 5., 50);
+        let noise = Normal::new(0., 0.05).sample_n(x.len());
+        let y = x
+            .iter()
+            .zip(&noise)
+            .map(|(xi, e)| a * (b * xi).exp() + e)
+            .collect::<Vec<_>>();
+
+        let resid = |p: &[f64]| {
+            x.iter()
+                .zip(&y)
+                .map(|(xi, yi)| yi - p[0] * (p[1] * xi).exp())
+                .collect::<Vec<_>>()
+        };
+        let opt = levenberg_marquardt(resid, &[1., 0.1], 200, 1e-10);
+        assert!((opt[0] - a).abs() < 0.05);
+        assert!((opt[1] - b).abs() < 0.01);
+    }
+}
@@ -1,8 +1,9 @@
-//! Various optimization algorithms (eg. Adam, SGD, Levenberg-Marquardt).
+//! Various optimization algorithms (eg. Adam, SGD, Levenberg-Marquardt, golden-section search).
 
 use autodiff::F1;
 
 mod adam;
+mod golden;
 mod lm;
 mod sgd;
 
@@ -27,5 +28,6 @@ pub enum GradFn {
 }
 
 pub use self::adam::*;
+pub use self::golden::*;
 pub use self::lm::*;
 pub use self::sgd::*;
@@ -0,0 +1,121 @@
+//! Transformations between bounded and unbounded parameter spaces, for optimizing functions of
+//! constrained parameters with unconstrained optimizers.
+
+use crate::functions::{logistic, logit};
+use crate::optimize::num_gradient::partial;
+use crate::optimize::optimizers::adam;
+
+/// Maps a parameter `x` in the interval `(lower, upper)` to the whole real line. Either bound may
+/// be infinite. A parameter bounded on one side is mapped with a log transform (`ln(x - lower)` or
+/// `ln(upper - x)`), a parameter bounded on both sides with a scaled logit transform, and an
+/// unbounded parameter is left unchanged.
+///
+/// # Errors
+/// Panics if `lower >= upper`.
+pub fn to_unbounded(x: f64, lower: f64, upper: f64) -> f64 {
+    assert!(lower < upper, "lower bound must be less than upper bound");
+    match (lower.is_finite(), upper.is_finite()) {
+        (false, false) => x,
+        (true, false) => (x - lower).ln(),
+        (false, true) => (upper - x).ln(),
+        (true, true) => logit((x - lower) / (upper - lower)),
+    }
+}
+
+/// Maps an unbounded value `y` back into the interval `(lower, upper)`. This is the inverse of
+/// [to_unbounded].
+///
+/// # Errors
+/// Panics if `lower >= upper`.
+pub fn from_unbounded(y: f64, lower: f64, upper: f64) -> f64 {
+    assert!(lower < upper, "lower bound must be less than upper bound");
+    match (lower.is_finite(), upper.is_finite()) {
+        (false, false) => y,
+        (true, false) => lower + y.exp(),
+        (false, true) => upper - y.exp(),
+        (true, true) => lower + (upper - lower) * logistic(y),
+    }
+}
+
+/// Minimizes a function `f` of parameters subject to box constraints, given as a `(lower, upper)`
+/// pair for each parameter (use infinite values for unbounded sides). The parameters are mapped
+/// to an unbounded space with [to_unbounded] and the transformed problem is minimized with [adam]
+/// using numerical gradients, so `f` is only evaluated within the bounds. Note that a parameter
+/// can round to one of its bounds exactly when its transformed value is large in magnitude.
+///
+/// # Errors
+/// Panics if `x0` and `bounds` have different lengths, or if any bound is invalid.
+pub fn minimize_bounded<F>(
+    f: F,
+    x0: &[f64],
+    bounds: &[(f64, f64)],
+    lr: f64,
+    max_iter: usize,
+) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    assert_eq!(
+        x0.len(),
+        bounds.len(),
+        "x0 and bounds must have the same length"
+    );
+
+    let to_bounded = |y: &[f64]| -> Vec<f64> {
+        y.iter()
+            .zip(bounds)
+            .map(|(&yi, &(l, u))| from_unbounded(yi, l, u))
+            .collect()
+    };
+    let g = |y: &[f64]| f(&to_bounded(y));
+    let grad = |y: &[f64]| (0..y.len()).map(|i| partial(g, y, i)).collect::<Vec<_>>();
+
+    let y0 = x0
+        .iter()
+        .zip(bounds)
+        .map(|(&xi, &(l, u))| to_unbounded(xi, l, u))
+        .collect::<Vec<_>>();
+
+    to_bounded(&adam(grad, &y0, lr, max_iter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_roundtrip() {
+        let inf = f64::INFINITY;
+        for &(x, l, u) in &[
+            (1.3, -inf, inf),
+            (0.2, 0., inf),
+            (-4., -inf, -1.),
+            (0.7, 0., 1.),
+            (2.5, -3., 4.),
+        ] {
+            assert_approx_eq!(from_unbounded(to_unbounded(x, l, u), l, u), x);
+        }
+        assert!(from_unbounded(-800., 0., inf) >= 0.);
+        assert!(from_unbounded(800., 0., 1.) <= 1.);
+    }
+
+    #[test]
+    fn test_minimize_bounded() {
+        // unconstrained optimum at (-1, 0.3), but the first parameter must be positive
+        let f = |x: &[f64]| {
+            assert!(x[0] >= 0. && x[1] > 0. && x[1] < 1.);
+            (x[0] + 1.).powi(2) + (x[1] - 0.3).powi(2)
+        };
+        let opt = minimize_bounded(f, &[2., 0.9], &[(0., f64::INFINITY), (0., 1.)], 0.05, 2000);
+        // the boundary is only approached asymptotically in the transformed space
+        assert!(opt[0] >= 0. && opt[0] < 1e-2);
+        assert_approx_eq!(opt[1], 0.3, 1e-4);
+    }
+
+    #[test]
+    #[should_panic(expected = "lower bound must be less than upper bound")]
+    fn test_bad_bounds() {
+        to_unbounded(0.5, 1., 0.);
+    }
+}
@@ -0,0 +1,79 @@
+//! Diagnostics for regression models.
+
+use crate::linalg::{invert_matrix, norm, xtx};
+use crate::statistics::mean;
+
+/// Calculates the [variance inflation factor](https://en.wikipedia.org/wiki/Variance_inflation_factor)
+/// of each predictor (column) of the `nrows x ncols` row-major matrix `x`. The variance inflation
+/// factor of a predictor is `1 / (1 - R^2)`, where `R^2` is the coefficient of determination from
+/// regressing that predictor on all of the others (with an intercept). These are the diagonal
+/// elements of the inverse of the correlation matrix of the predictors, which is how they are
+/// computed here. Values much larger than 1 (commonly, above 5 or 10) indicate multicollinearity.
+///
+/// # Remarks
+/// `x` should not include an intercept column.
+///
+/// # Errors
+/// Panics if the shape of `x` does not match `nrows` and `ncols`, or if any column is constant.
+pub fn vif(x: &[f64], nrows: usize, ncols: usize) -> Vec<f64> {
+    assert_eq!(x.len(), nrows * ncols, "matrix shape mismatch");
+
+    // center the columns and scale them to unit length, so that Z^T Z is the correlation matrix
+    let mut z = x.to_vec();
+    for j in 0..ncols {
+        let col = (0..nrows).map(|i| x[i * ncols + j]).collect::<Vec<_>>();
+        let m = mean(&col);
+        let scale = norm(&col.iter().map(|v| v - m).collect::<Vec<_>>());
+        assert!(scale > 0., "columns must not be constant");
+        for i in 0..nrows {
+            z[i * ncols + j] = (x[i * ncols + j] - m) / scale;
+        }
+    }
+
+    let corr_inv = invert_matrix(&xtx(&z, nrows));
+    (0..ncols).map(|j| corr_inv[j * ncols + j]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{Distribution1D, Normal};
+    use crate::predict::ridge;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_vif() {
+        alea::set_seed(14);
+        let n = 200;
+        let a = Normal::new(0., 1.).sample_n(n);
+        let b = Normal::new(0., 1.).sample_n(n);
+        let c = Normal::new(0., 1.).sample_n(n);
+        let noise = Normal::new(0., 0.05).sample_n(n);
+
+        // a and a + noise are nearly collinear
+        let x = (0..n)
+            .flat_map(|i| vec![a[i], a[i] + noise[i], b[i]])
+            .collect::<Vec<_>>();
+        let factors = vif(&x, n, 3);
+        assert!(factors[0] > 100. && factors[1] > 100.);
+        assert!(factors[2] < 1.1);
+
+        // compare with regressing the last predictor on the others
+        let others = (0..n)
+            .flat_map(|i| vec![1., a[i], a[i] + noise[i]])
+            .collect::<Vec<_>>();
+        let coef = ridge(&others, &b, 0., n, 3);
+        let ss_res = (0..n)
+            .map(|i| {
+                (b[i] - coef[0] - coef[1] * others[i * 3 + 1] - coef[2] * others[i * 3 + 2]).powi(2)
+            })
+            .sum::<f64>();
+        let ss_tot = b.iter().map(|v| (v - mean(&b)).powi(2)).sum::<f64>();
+        assert_approx_eq!(factors[2], ss_tot / ss_res, 1e-8);
+
+        let x = (0..n)
+            .flat_map(|i| vec![a[i], b[i], c[i]])
+            .collect::<Vec<_>>();
+        assert!(vif(&x, n, 3).iter().all(|&v| (1. ..1.1).contains(&v)));
+    }
+}
@@ -4,6 +4,7 @@ use crate::linalg::{norm, svsub, vexp, vmul, vrecip, vsmul, vssub, vsub};
 /// family](https://en.wikipedia.org/wiki/Exponential_family) set of distributions. These are
 /// intended for use with [GLM](../predict/struct.GLM.html).
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExponentialFamily {
     Gaussian,
     Bernoulli,
@@ -58,50 +59,41 @@ impl ExponentialFamily {
         }
     }
 
+    /// Calculates the deviance of the fitted means `mu` for the observations `y`. See
+    /// [weighted_deviance](ExponentialFamily::weighted_deviance) to give the observations different
+    /// weights.
     pub fn deviance(&self, y: &[f64], mu: &[f64]) -> f64 {
+        self.weighted_deviance(y, mu, &vec![1.; y.len()])
+    }
+
+    /// Calculates the deviance of the fitted means `mu` for the observations `y`, where the
+    /// contribution of each observation is multiplied by its prior weight. With integer weights,
+    /// this is the same as the deviance of the data with each observation repeated that many times.
+    pub fn weighted_deviance(&self, y: &[f64], mu: &[f64], weights: &[f64]) -> f64 {
         let n = y.len();
         assert_eq!(n, mu.len());
-        match self {
-            ExponentialFamily::Gaussian => norm(&vsub(y, mu)),
-            ExponentialFamily::Bernoulli => {
-                (0..n)
-                    .map(|i| y[i] * mu[i].ln() + (1. - y[i]) * (1. - mu[i]).ln())
-                    .sum::<f64>()
-                    * -2.
-            }
-            ExponentialFamily::QuasiPoisson => {
-                let ylogy = y
-                    .iter()
-                    .map(|x| if *x == 0. { 0. } else { x * x.ln() })
-                    .collect::<Vec<_>>();
-                2. * (0..y.len())
-                    .map(|i| mu[i] - y[i] - y[i] * mu[i].ln() + ylogy[i])
-                    .sum::<f64>()
-            }
-            ExponentialFamily::Poisson => {
+        assert_eq!(n, weights.len(), "wrong number of weights");
+        let unit_deviances = match self {
+            ExponentialFamily::Gaussian => vsub(y, mu).iter().map(|r| r * r).collect::<Vec<_>>(),
+            ExponentialFamily::Bernoulli => (0..n)
+                .map(|i| -2. * (xlogy(y[i], mu[i]) + xlogy(1. - y[i], 1. - mu[i])))
+                .collect(),
+            ExponentialFamily::QuasiPoisson | ExponentialFamily::Poisson => {
                 let ylogy = y
                     .iter()
                     .map(|x| if *x == 0. { 0. } else { x * x.ln() })
                     .collect::<Vec<_>>();
-                2. * (0..y.len())
-                    .map(|i| mu[i] - y[i] - y[i] * mu[i].ln() + ylogy[i])
-                    .sum::<f64>()
-            }
-            ExponentialFamily::Gamma => {
-                2. * (y
-                    .iter()
-                    .zip(mu)
-                    .map(|(yv, muv)| (yv - muv) / (muv) - (yv / muv).ln())
-                    .sum::<f64>())
-            }
-            ExponentialFamily::Exponential => {
-                2. * (y
-                    .iter()
-                    .zip(mu)
-                    .map(|(yv, muv)| (yv - muv) / (muv) - (yv / muv).ln())
-                    .sum::<f64>())
+                (0..n)
+                    .map(|i| 2. * (mu[i] - y[i] - y[i] * mu[i].ln() + ylogy[i]))
+                    .collect()
             }
-        }
+            ExponentialFamily::Gamma | ExponentialFamily::Exponential => y
+                .iter()
+                .zip(mu)
+                .map(|(yv, muv)| 2. * ((yv - muv) / (muv) - (yv / muv).ln()))
+                .collect(),
+        };
+        unit_deviances.iter().zip(weights).map(|(d, w)| w * d).sum()
     }
 
     pub fn initial_working_response(&self, y: &[f64]) -> Option<Vec<f64>> {
@@ -127,7 +119,24 @@ impl ExponentialFamily {
         }
     }
 
-    pub fn penalized_deviance(&self, y: &[f64], mu: &[f64], alpha: f64, coef: &[f64]) -> f64 {
-        self.deviance(y, mu) + alpha * norm(&coef[1..])
+    pub fn penalized_deviance(
+        &self,
+        y: &[f64],
+        mu: &[f64],
+        weights: &[f64],
+        alpha: f64,
+        coef: &[f64],
+    ) -> f64 {
+        self.weighted_deviance(y, mu, weights) + alpha * norm(&coef[1..])
+    }
+}
+
+/// Calculates `x * ln(y)`, taking it to be 0 when `x` is 0 (so that observations that are fitted
+/// exactly do not make the deviance NaN).
+fn xlogy(x: f64, y: f64) -> f64 {
+    if x == 0. {
+        0.
+    } else {
+        x * y.ln()
     }
 }
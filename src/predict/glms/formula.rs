@@ -1,18 +1,281 @@
 use std::collections::HashMap;
 
+/// A model formula in the style of R (e.g., `"y ~ x1 + log(x2) + x1:x3"`), together with the data
+/// that the variables in it refer to. It is used to build the response vector and the design
+/// matrix for a model; see [GLM::fit_with_formula](crate::predict::GLM::fit_with_formula).
+///
+/// The right-hand side is a sum of terms separated by `+`, where each term is one of
+///
+/// - a variable name, e.g. `x`,
+/// - a transformed variable: `log(x)`, `exp(x)`, or `sqrt(x)`,
+/// - a polynomial, `poly(x, d)`, which expands to the columns `x, x^2, ..., x^d`,
+/// - an interaction, `a:b`, which is the elementwise product of the terms `a` and `b`, or
+/// - a full cross, `a*b`, which is shorthand for `a + b + a:b` (and similarly for more factors).
+///
+/// An intercept column is always included as the first column of the design matrix, and repeated
+/// columns are only included once.
 #[derive(Debug, Clone)]
 pub struct Formula<'a, 'b> {
     formula: &'a str,
     data: HashMap<&'b str, Vec<f64>>,
 }
 
+type Columns = Vec<(String, Vec<f64>)>;
+
 impl<'a, 'b> Formula<'a, 'b> {
     pub fn new(formula: &'a str, data: HashMap<&'b str, Vec<f64>>) -> Self {
         Self { formula, data }
     }
 
+    /// Build the row-major design matrix given by the right-hand side of the formula. The number
+    /// of columns is the length of [column_names](Formula::column_names).
     pub fn parse(&self) -> Result<Vec<f64>, &'static str> {
-        assert!(self.formula.contains("~"), "Formula must contain ~.");
-        todo!()
+        let columns = self.columns()?;
+        let n = columns[0].1.len();
+        let mut x = Vec::with_capacity(n * columns.len());
+        for i in 0..n {
+            for (_, col) in &columns {
+                x.push(col[i]);
+            }
+        }
+        Ok(x)
+    }
+
+    /// Return the names of the columns of the design matrix, starting with `"(Intercept)"`.
+    pub fn column_names(&self) -> Result<Vec<String>, &'static str> {
+        Ok(self.columns()?.into_iter().map(|(name, _)| name).collect())
+    }
+
+    /// Return the response vector given by the left-hand side of the formula.
+    pub fn response(&self) -> Result<Vec<f64>, &'static str> {
+        let (lhs, _) = self.sides()?;
+        let mut cols = self.eval_factor(lhs)?;
+        if cols.len() != 1 {
+            return Err("response must be a single column");
+        }
+        Ok(cols.remove(0).1)
+    }
+
+    fn sides(&self) -> Result<(&'a str, &'a str), &'static str> {
+        let sides = self.formula.split('~').collect::<Vec<_>>();
+        if sides.len() != 2 {
+            return Err("formula must contain exactly one ~");
+        }
+        Ok((sides[0].trim(), sides[1].trim()))
+    }
+
+    fn columns(&self) -> Result<Columns, &'static str> {
+        let (_, rhs) = self.sides()?;
+        let n = self.response()?.len();
+
+        let mut columns: Columns = vec![("(Intercept)".to_string(), vec![1.; n])];
+        for term in expand_terms(rhs)? {
+            for (name, col) in self.eval_term(&term)? {
+                if col.len() != n {
+                    return Err("variables must all have the same length");
+                }
+                if !columns.iter().any(|(existing, _)| *existing == name) {
+                    columns.push((name, col));
+                }
+            }
+        }
+        Ok(columns)
+    }
+
+    /// Evaluates an interaction of factors separated by `:`, taking the products of all
+    /// combinations of their columns.
+    fn eval_term(&self, term: &str) -> Result<Columns, &'static str> {
+        let mut columns: Columns = vec![];
+        for factor in split_top_level(term, ':') {
+            let factor_cols = self.eval_factor(factor)?;
+            if columns.is_empty() {
+                columns = factor_cols;
+                continue;
+            }
+            let mut crossed = Vec::with_capacity(columns.len() * factor_cols.len());
+            for (name1, col1) in &columns {
+                for (name2, col2) in &factor_cols {
+                    if col1.len() != col2.len() {
+                        return Err("variables must all have the same length");
+                    }
+                    let col = col1.iter().zip(col2).map(|(a, b)| a * b).collect();
+                    crossed.push((format!("{}:{}", name1, name2), col));
+                }
+            }
+            columns = crossed;
+        }
+        Ok(columns)
+    }
+
+    /// Evaluates a single variable, possibly wrapped in a transformation.
+    fn eval_factor(&self, factor: &str) -> Result<Columns, &'static str> {
+        let factor = factor.trim();
+        if factor.is_empty() {
+            return Err("empty term in formula");
+        }
+
+        let open = match factor.find('(') {
+            Some(open) if factor.ends_with(')') => open,
+            _ => {
+                return match self.data.get(factor) {
+                    Some(col) => Ok(vec![(factor.to_string(), col.clone())]),
+                    None => Err("variable not found in data"),
+                };
+            }
+        };
+        let func = factor[..open].trim();
+        let args = &factor[open + 1..factor.len() - 1];
+
+        if func == "poly" {
+            let args = split_top_level(args, ',');
+            if args.len() != 2 {
+                return Err("poly takes a variable and a degree");
+            }
+            let degree = args[1]
+                .trim()
+                .parse::<i32>()
+                .map_err(|_| "poly degree must be a positive integer")?;
+            if degree < 1 {
+                return Err("poly degree must be a positive integer");
+            }
+            let (name, col) = self.eval_single(args[0])?;
+            return Ok((1..=degree)
+                .map(|d| {
+                    let name = if d == 1 {
+                        name.clone()
+                    } else {
+                        format!("{}^{}", name, d)
+                    };
+                    (name, col.iter().map(|x| x.powi(d)).collect())
+                })
+                .collect());
+        }
+
+        let f: fn(f64) -> f64 = match func {
+            "log" => f64::ln,
+            "exp" => f64::exp,
+            "sqrt" => f64::sqrt,
+            _ => return Err("unknown function in formula"),
+        };
+        let (_, col) = self.eval_single(args)?;
+        Ok(vec![(factor.to_string(), col.into_iter().map(f).collect())])
+    }
+
+    fn eval_single(&self, factor: &str) -> Result<(String, Vec<f64>), &'static str> {
+        let mut cols = self.eval_factor(factor)?;
+        if cols.len() != 1 {
+            return Err("function arguments must be a single column");
+        }
+        Ok(cols.remove(0))
+    }
+}
+
+/// Splits a string on a separator, ignoring separators inside parentheses.
+fn split_top_level(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c == sep && depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts
+}
+
+/// Splits the right-hand side of a formula into terms, expanding full crosses (`a*b`) into their
+/// main effects and interactions (`a`, `b`, `a:b`).
+fn expand_terms(rhs: &str) -> Result<Vec<String>, &'static str> {
+    let mut terms = vec![];
+    for term in split_top_level(rhs, '+') {
+        if term.is_empty() {
+            return Err("empty term in formula");
+        }
+        if term == "1" {
+            // the intercept is always included
+            continue;
+        }
+        let factors = split_top_level(term, '*');
+        let k = factors.len();
+        // all non-empty subsets of the factors, ordered by size
+        let mut subsets = (1..(1_usize << k))
+            .map(|mask| (0..k).filter(|i| mask >> i & 1 == 1).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        subsets.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        for subset in subsets {
+            terms.push(
+                subset
+                    .iter()
+                    .map(|&i| factors[i])
+                    .collect::<Vec<_>>()
+                    .join(":"),
+            );
+        }
+    }
+    Ok(terms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> HashMap<&'static str, Vec<f64>> {
+        let mut data = HashMap::new();
+        data.insert("y", vec![1., 2., 3., 4., 5.]);
+        data.insert("x1", vec![0.5, 1., 1.5, 2., 2.5]);
+        data.insert("x2", vec![2., -1., 0., 3., 1.]);
+        data.insert("x3", vec![1., 2., 4., 8., 16.]);
+        data
+    }
+
+    #[test]
+    fn test_formula_cross() {
+        let formula = Formula::new("y ~ x1*x2", data());
+        assert_eq!(
+            formula.column_names().unwrap(),
+            vec!["(Intercept)", "x1", "x2", "x1:x2"]
+        );
+        let x = formula.parse().unwrap();
+        assert_eq!(x.len(), 5 * 4);
+        assert_eq!(&x[4..8], &[1., 1., -1., -1.]);
+        assert_eq!(formula.response().unwrap(), data()["y"]);
+    }
+
+    #[test]
+    fn test_formula_transforms() {
+        let formula = Formula::new("log(y) ~ x1 + x1:x2 + log(x3) + poly(x2, 3) + 1", data());
+        let names = [
+            "(Intercept)",
+            "x1",
+            "x1:x2",
+            "log(x3)",
+            "x2",
+            "x2^2",
+            "x2^3",
+        ];
+        assert_eq!(formula.column_names().unwrap(), names);
+        let x = formula.parse().unwrap();
+        assert_eq!(x.len(), 5 * 7);
+        assert_eq!(&x[7..14], &[1., 1., -1., 2_f64.ln(), -1., 1., -1.]);
+        assert_eq!(formula.response().unwrap()[1], 2_f64.ln());
+
+        let formula = Formula::new("y ~ x1*x2*x3", data());
+        assert_eq!(formula.column_names().unwrap().len(), 8);
+    }
+
+    #[test]
+    fn test_formula_errors() {
+        assert!(Formula::new("y + x1", data()).parse().is_err());
+        assert!(Formula::new("y ~ x4", data()).parse().is_err());
+        assert!(Formula::new("y ~ x1 + ", data()).parse().is_err());
+        assert!(Formula::new("y ~ foo(x1)", data()).parse().is_err());
+        assert!(Formula::new("y ~ poly(x1, 0)", data()).parse().is_err());
     }
 }
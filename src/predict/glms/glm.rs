@@ -1,13 +1,18 @@
 use crate::prelude::{
-    diag, invert_matrix, is_design, is_matrix, matmul, mean, solve, sum, svmul, vadd, vdiv, vmul,
-    vsqrt, vsub,
+    diag, invert_matrix, is_design, is_matrix, matmul, normal_pvalue_two_sided, solve, sum, svmul,
+    vadd, vdiv, vmul, vsqrt, vsub,
 };
 
-use super::ExponentialFamily;
-// use super::Formula;
+use super::{ExponentialFamily, Formula};
+use crate::distributions::{Normal, T};
+use std::fmt::{Display, Formatter};
+
+/// The maximum number of times that a scoring step is halved in each iteration of [GLM::fit].
+const MAX_STEP_HALVINGS: usize = 30;
 
 /// Implements a [generalized linear model](https://en.wikipedia.org/wiki/Generalized_linear_model).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GLM {
     pub family: ExponentialFamily,
     pub alpha: f64,
@@ -16,7 +21,10 @@ pub struct GLM {
     offsets: Option<Vec<f64>>,
     coef: Option<Vec<f64>>,
     deviance: Option<f64>,
+    pearson_chi2: Option<f64>,
     information_matrix: Option<Vec<f64>>,
+    /// Names of the columns of the design matrix, if the model was fitted with a [Formula].
+    column_names: Option<Vec<String>>,
     n: Option<usize>,
     p: Option<usize>,
 }
@@ -34,7 +42,9 @@ impl GLM {
             offsets: None,
             coef: None,
             deviance: None,
+            pearson_chi2: None,
             information_matrix: None,
+            column_names: None,
             n: None,
             p: None,
         }
@@ -64,9 +74,15 @@ impl GLM {
         self
     }
 
-    // fn fit_with_formula<'a>(&self, formula: Formula, data: HashMap<&'a str, Vec<f64>>) {
-    //     todo!();
-    // }
+    /// Fit the GLM using the response and design matrix described by a [Formula]. See
+    /// [fit](GLM::fit). The names of the columns are used to label the coefficients in the
+    /// [summary](GLM::summary).
+    pub fn fit_with_formula(&mut self, formula: &Formula, max_iter: usize) -> Result<(), &str> {
+        let y = formula.response()?;
+        let x = formula.parse()?;
+        let column_names = formula.column_names()?;
+        self.fit_with_names(&x, &y, max_iter, Some(column_names))
+    }
 
     fn has_converged(&self, loss: f64, loss_previous: f64, tolerance: f64) -> bool {
         if loss_previous.is_infinite() {
@@ -131,10 +147,47 @@ impl GLM {
         }
     }
 
+    /// Returns the linear predictor (including any offsets) and the mean response for the
+    /// coefficients `coef`. For the Bernoulli family, the mean is kept away from 0 and 1 so that
+    /// the variance and the working weights used while fitting are never 0.
+    fn linear_predictor_and_mean(
+        &self,
+        x: &[f64],
+        coef: &[f64],
+        n: usize,
+        p: usize,
+    ) -> (Vec<f64>, Vec<f64>) {
+        let mut eta = matmul(x, coef, n, p, false, false);
+        if let Some(offset) = &self.offsets {
+            assert_eq!(offset.len(), n, "wrong number of offsets");
+            eta = vadd(&eta, offset);
+        }
+        let mut mu = self.family.inv_link(&eta);
+        if let ExponentialFamily::Bernoulli = self.family {
+            mu.iter_mut()
+                .for_each(|m| *m = m.clamp(f64::EPSILON, 1. - f64::EPSILON));
+        }
+        (eta, mu)
+    }
+
     /// Fit the GLM using the [scoring algorithm](https://en.wikipedia.org/wiki/Score_(statistics)#Scoring_algorithm),
     /// which gives the maximumum likelihood estimate. It performs a maximum of `max_iter` iterations.
     /// Note that `x` must be a design matrix (i.e., the first column must contain all 1's).
+    ///
+    /// If a full scoring step would increase the (penalized) deviance, the step is repeatedly
+    /// halved (up to 30 times) before it is taken, which keeps the fit from diverging on
+    /// ill-conditioned or nearly separable data.
     pub fn fit(&mut self, x: &[f64], y: &[f64], max_iter: usize) -> Result<(), &str> {
+        self.fit_with_names(x, y, max_iter, None)
+    }
+
+    fn fit_with_names(
+        &mut self,
+        x: &[f64],
+        y: &[f64],
+        max_iter: usize,
+        column_names: Option<Vec<String>>,
+    ) -> Result<(), &str> {
         // check that the matrices are the right sizes
         let n = y.len();
         let p = is_matrix(x, n).unwrap();
@@ -147,60 +200,52 @@ impl GLM {
             vec![1.; n]
         };
 
-        let initial_intercept = mean(y);
+        let initial_intercept = sum(&vmul(&weights, y)) / sum(&weights);
         let mut coef = vec![0.; p];
         coef[0] = initial_intercept;
 
-        let mut penalized_deviance = f64::INFINITY;
+        let (mut eta, mut mu) = self.linear_predictor_and_mean(x, &coef, n, p);
+        let mut penalized_deviance = self
+            .family
+            .penalized_deviance(y, &mu, &weights, self.alpha, &coef);
         let mut is_converged;
         let mut n_iter = 0;
 
-        let mut eta;
-        let mut mu;
-        let mut dmu;
-        let mut var;
-        let mut dbeta;
-        let mut ddbeta;
-
         loop {
-            // println!("{} {:?}", n_iter, coef);
-            eta = matmul(x, &coef, n, p, false, false);
-            if let Some(offset) = &self.offsets {
-                assert_eq!(offset.len(), n, "wrong number of offsets");
-                eta = vadd(&eta, offset);
-            }
-            // println!("eta {:?}", eta);
-
-            mu = self.family.inv_link(&eta);
-            // println!("mu {:?}", mu);
-            dmu = self.family.d_inv_link(&eta, &mu);
-            // println!("dmu {:?}", dmu);
-            var = self.family.variance(&mu);
-            // println!("var {:?}", var);
+            let dmu = self.family.d_inv_link(&eta, &mu);
+            let var = self.family.variance(&mu);
 
-            dbeta = self.compute_dbeta(x, y, &mu, &dmu, &var, &weights);
-            ddbeta = self.compute_ddbeta(x, &dmu, &var, &weights);
+            let mut dbeta = self.compute_dbeta(x, y, &mu, &dmu, &var, &weights);
+            let mut ddbeta = self.compute_ddbeta(x, &dmu, &var, &weights);
 
-            // println!("dbeta {:?}", dbeta);
-            // println!("ddbeta {:?}", ddbeta);
-
-            // println!("coef before penalty {:?}", coef);
             if self.alpha > 0. {
                 self.apply_dbeta_penalty(&mut dbeta, &coef);
                 self.apply_ddbeta_penalty(&mut ddbeta, p);
             }
 
-            // println!("dbeta {:?}", dbeta);
-            // println!("ddbeta {:?}", ddbeta);
-
-            // println!("solve {:?}", solve(&ddbeta, &dbeta));
-            coef = vsub(&coef, &solve(&ddbeta, &dbeta));
-
-            // println!("coef {:?}", coef);
+            // the full scoring step can overshoot (e.g., for nearly separable data), so it is halved
+            // until the penalized deviance no longer increases
+            let mut step = solve(&ddbeta, &dbeta);
+            let mut n_halvings = 0;
+            let (new_coef, new_eta, new_mu, new_penalized_deviance) = loop {
+                let new_coef = vsub(&coef, &step);
+                let (new_eta, new_mu) = self.linear_predictor_and_mean(x, &new_coef, n, p);
+                let new_penalized_deviance = self
+                    .family
+                    .penalized_deviance(y, &new_mu, &weights, self.alpha, &new_coef);
+                if new_penalized_deviance <= penalized_deviance || n_halvings >= MAX_STEP_HALVINGS {
+                    break (new_coef, new_eta, new_mu, new_penalized_deviance);
+                }
+                step = svmul(0.5, &step);
+                n_halvings += 1;
+            };
 
             let penalized_deviance_previous = penalized_deviance;
+            coef = new_coef;
+            eta = new_eta;
+            mu = new_mu;
+            penalized_deviance = new_penalized_deviance;
 
-            penalized_deviance = self.family.penalized_deviance(y, &mu, self.alpha, &coef);
             is_converged = self.has_converged(
                 penalized_deviance,
                 penalized_deviance_previous,
@@ -213,9 +258,17 @@ impl GLM {
             }
         }
 
+        let dmu = self.family.d_inv_link(&eta, &mu);
+        let var = self.family.variance(&mu);
         self.coef = Some(coef);
-        self.deviance = Some(self.family.deviance(y, &mu));
+        self.deviance = Some(self.family.weighted_deviance(y, &mu, &weights));
+        self.pearson_chi2 = Some(
+            (0..n)
+                .map(|i| weights[i] * (y[i] - mu[i]).powi(2) / var[i])
+                .sum(),
+        );
         self.information_matrix = Some(self.compute_ddbeta(x, &dmu, &var, &weights));
+        self.column_names = column_names;
         self.n = Some(sum(&weights).round() as usize);
         self.p = Some(p);
 
@@ -256,15 +309,18 @@ impl GLM {
         Ok(dev + self.p.unwrap() as f64 * (self.n.unwrap() as f64).ln())
     }
 
-    /// Calculates the dispersion of the model.
+    /// Calculates the dispersion of the model. For families with a dispersion parameter (e.g.,
+    /// Gaussian and Gamma), this is the Pearson chi-squared estimate `sum(w * (y - mu)^2 / V(mu)) /
+    /// (n - p)` from the fitted data, which scales the [covariance
+    /// matrix](GLM::coef_covariance_matrix) and the standard errors. For the other families (e.g.,
+    /// Bernoulli and Poisson), the dispersion is fixed at 1.
     pub fn dispersion(&self) -> Result<f64, &str> {
-        let dev = self.deviance()?;
+        let pearson_chi2 = self.pearson_chi2.ok_or("model has not been fitted yet")?;
         if self.family.has_dispersion() {
-            // ok to unwrap because if deviance is Some then these are also Some
-            // and deviance can only be Some after `fit` because it is private
+            // ok to unwrap because these are set along with pearson_chi2 in `fit`
             let n = self.n.unwrap();
             let p = self.p.unwrap();
-            Ok(dev / (n - p) as f64)
+            Ok(pearson_chi2 / (n - p) as f64)
         } else {
             Ok(1.)
         }
@@ -287,6 +343,88 @@ impl GLM {
         Ok(vsqrt(&variances))
     }
 
+    /// Returns a regression table for the fitted model, similar to R's `summary.glm`. For each
+    /// coefficient, it lists the estimate, its standard error, the Wald statistic, and its
+    /// two-sided p-value, followed by the dispersion, the deviance, and the AIC. For families with
+    /// an estimated dispersion (e.g., Gaussian and Gamma), these are t statistics with `n - p`
+    /// degrees of freedom; otherwise, they are z statistics compared to the normal distribution.
+    /// The coefficients are labelled with the column names of the [Formula] if the model was
+    /// fitted with [fit_with_formula](GLM::fit_with_formula), and `x1, x2, ...` otherwise.
+    pub fn summary(&self) -> String {
+        let (coef, errors) = match (self.coef(), self.coef_standard_error()) {
+            (Ok(coef), Ok(errors)) => (coef, errors),
+            _ => {
+                return format!(
+                    "Generalized linear model (family: {:?}), model not fitted",
+                    self.family
+                )
+            }
+        };
+        // ok to unwrap because these are set along with coef in `fit`
+        let n = self.n.unwrap();
+        let p = self.p.unwrap();
+        let df = n.saturating_sub(p);
+
+        let names = match &self.column_names {
+            Some(names) => names.clone(),
+            None => (0..p)
+                .map(|i| {
+                    if i == 0 {
+                        "(Intercept)".to_string()
+                    } else {
+                        format!("x{}", i)
+                    }
+                })
+                .collect(),
+        };
+        let width = names.iter().map(|name| name.len() + 1).fold(12, usize::max);
+        let (stat_label, pvalue_label) = if self.family.has_dispersion() {
+            ("t value", "Pr(>|t|)")
+        } else {
+            ("z value", "Pr(>|z|)")
+        };
+
+        let mut lines = vec![
+            format!("Generalized linear model (family: {:?})", self.family),
+            format!(
+                "{:<width$}{:>12}{:>12}{:>12}{:>12}",
+                "",
+                "Estimate",
+                "Std. Error",
+                stat_label,
+                pvalue_label,
+                width = width
+            ),
+        ];
+        for ((name, c), se) in names.iter().zip(coef).zip(&errors) {
+            let stat = c / se;
+            let pvalue = if self.family.has_dispersion() {
+                T::try_new(df as f64).map_or(f64::NAN, |t| 2. * t.cdf(-stat.abs()))
+            } else {
+                normal_pvalue_two_sided(stat)
+            };
+            lines.push(format!(
+                "{:<width$}{:>12.4}{:>12.4}{:>12.3}{:>12.4}",
+                name,
+                c,
+                se,
+                stat,
+                pvalue,
+                width = width
+            ));
+        }
+        // ok to unwrap because the model has been fitted
+        lines.push(format!("Dispersion: {:.4}", self.dispersion().unwrap()));
+        lines.push(format!(
+            "Deviance: {:.4} on {} degrees of freedom",
+            self.deviance().unwrap(),
+            df
+        ));
+        lines.push(format!("AIC: {:.4}", self.aic().unwrap()));
+
+        lines.join("\n")
+    }
+
     /// Use the fitted model to make predictions on some new data.
     pub fn predict(&self, x: &[f64]) -> Result<Vec<f64>, &str> {
         let coef = self.coef()?;
@@ -300,6 +438,54 @@ impl GLM {
         }
     }
 
+    /// Use the fitted model to make predictions on some new data, along with an interval for each
+    /// prediction at the given confidence `level` (e.g., 0.95). The uncertainty in the estimated
+    /// coefficients (from the inverse information matrix) is propagated to the linear predictor,
+    /// and the resulting normal interval is mapped through the inverse link function, so the
+    /// interval is on the scale of the response (e.g., probabilities for the Bernoulli family).
+    ///
+    /// # Remarks
+    /// For the Gaussian family, the estimated noise variance is also included, which gives the
+    /// classic prediction interval for a new observation (using a normal rather than a Student's t
+    /// quantile). For other families, this is a confidence interval for the mean response.
+    pub fn predict_interval(&self, x: &[f64], level: f64) -> Result<Vec<(f64, f64)>, &str> {
+        assert!(level > 0. && level < 1., "level must be between 0 and 1");
+        let coef = self.coef()?;
+        let cov = self.coef_covariance_matrix()?;
+        let p = self.p.unwrap();
+        let n = is_matrix(x, p).unwrap();
+        assert!(is_design(x, n), "x is not a valid design matrix");
+
+        let mut eta = matmul(x, coef, n, p, false, false);
+        if let Some(offset) = &self.offsets {
+            eta = vadd(&eta, offset);
+        }
+        let noise_var = match self.family {
+            ExponentialFamily::Gaussian => self.dispersion()?,
+            _ => 0.,
+        };
+        let z = Normal::new(0., 1.).inv_cdf(0.5 + level / 2.);
+
+        let intervals = (0..n)
+            .map(|i| {
+                let row = &x[i * p..(i + 1) * p];
+                let var_eta = (0..p)
+                    .map(|j| row[j] * (0..p).map(|k| cov[j * p + k] * row[k]).sum::<f64>())
+                    .sum::<f64>();
+                let half_width = z * (var_eta + noise_var).sqrt();
+                (eta[i] - half_width, eta[i] + half_width)
+            })
+            .collect::<Vec<_>>();
+
+        let lower = self
+            .family
+            .inv_link(&intervals.iter().map(|b| b.0).collect::<Vec<_>>());
+        let upper = self
+            .family
+            .inv_link(&intervals.iter().map(|b| b.1).collect::<Vec<_>>());
+        Ok(lower.into_iter().zip(upper).collect())
+    }
+
     /// Make some new predictions and calculate the score of those predictions based on known
     /// responses.
     pub fn score(&self, x: &[f64], y: &[f64]) -> f64 {
@@ -307,6 +493,12 @@ impl GLM {
     }
 }
 
+impl Display for GLM {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.summary())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,4 +536,301 @@ mod tests {
         assert_approx_eq!(new_obs_pred[3], 0.87, 1e-1);
         assert_approx_eq!(new_obs_pred[4], 0.97, 1e-1);
     }
+
+    #[test]
+    fn test_glm_summary() {
+        let x = vec![
+            0.50, 0.75, 1.00, 1.25, 1.50, 1.75, 1.75, 2.00, 2.25, 2.50, 2.75, 3.00, 3.25, 3.50,
+            4.00, 4.25, 4.50, 4.75, 5.00, 5.50,
+        ];
+        let y = vec![
+            0., 0., 0., 0., 0., 0., 1., 0., 1., 0., 1., 0., 1., 0., 1., 1., 1., 1., 1., 1.,
+        ];
+        let n = y.len();
+        let xd = design(&x, n);
+
+        let mut glm = GLM::new(ExponentialFamily::Bernoulli);
+        assert!(glm.summary().contains("not fitted"));
+        assert_eq!(glm.summary(), glm.to_string());
+
+        glm.fit(&xd, &y, 25).unwrap();
+        let summary = glm.summary();
+        assert_eq!(summary, glm.to_string());
+        assert!(summary.contains("z value"));
+        assert!(summary.contains("x1"));
+        // title, column headers, one line per coefficient, dispersion, deviance, and AIC
+        assert_eq!(summary.lines().count(), 2 + 2 + 3);
+        assert!(summary.contains("-4.0777"));
+        assert!(summary.contains("1.5046"));
+        assert!(summary.contains("1.7610"));
+        assert!(summary.contains("on 18 degrees of freedom"));
+    }
+
+    #[test]
+    fn test_glm_summary_formula() {
+        use std::collections::HashMap;
+
+        let mut data = HashMap::new();
+        data.insert("y", vec![1.2, 1.9, 3.2, 3.8, 5.3, 5.7]);
+        data.insert("dose", vec![1., 2., 3., 4., 5., 6.]);
+        data.insert("temp", vec![0.3, -1.1, 0.8, 0.2, -0.5, 0.9]);
+        let formula = Formula::new("y ~ dose + temp", data);
+
+        let mut glm = GLM::new(ExponentialFamily::Gaussian);
+        glm.fit_with_formula(&formula, 25).unwrap();
+        let summary = glm.summary();
+        let lines = summary.lines().collect::<Vec<_>>();
+        assert!(lines[1].contains("t value") && lines[1].contains("Pr(>|t|)"));
+        // p-values from the t distribution with 3 degrees of freedom, as in R's summary.glm
+        assert!(lines[2].starts_with("(Intercept)") && lines[2].ends_with("0.5661"));
+        assert!(lines[3].starts_with("dose") && lines[3].ends_with("0.0010"));
+        assert!(lines[4].starts_with("temp") && lines[4].ends_with("0.9433"));
+        assert!(summary.contains("on 3 degrees of freedom"));
+
+        // refitting without the formula drops the names
+        let x = formula.parse().unwrap();
+        glm.fit(&x, &formula.response().unwrap(), 25).unwrap();
+        assert!(glm.summary().lines().nth(3).unwrap().starts_with("x1"));
+    }
+
+    #[test]
+    fn test_glm_predict_interval() {
+        use crate::distributions::{Distribution1D, Uniform};
+
+        alea::set_seed(11);
+        let n = 200;
+        let x = Uniform::new(0., 10.).sample_n(n);
+        let noise = Normal::new(0., 0.5).sample_n(n);
+        let y = (0..n)
+            .map(|i| 1. + 2. * x[i] + noise[i])
+            .collect::<Vec<_>>();
+        let mut glm = GLM::new(ExponentialFamily::Gaussian);
+        glm.fit(&design(&x, n), &y, 25).unwrap();
+
+        let new_x = design(&[0.5, 5., 9.5], 3);
+        let pred = glm.predict(&new_x).unwrap();
+        let narrow = glm.predict_interval(&new_x, 0.5).unwrap();
+        let wide = glm.predict_interval(&new_x, 0.99).unwrap();
+        for i in 0..3 {
+            assert!(narrow[i].0 < pred[i] && pred[i] < narrow[i].1);
+            assert!(wide[i].0 < narrow[i].0 && narrow[i].1 < wide[i].1);
+        }
+
+        // about 95% of new observations should fall inside their 95% prediction intervals
+        let xs = Uniform::new(0., 10.).sample_n(1000);
+        let ys = Normal::new(0., 0.5).sample_n(1000);
+        let intervals = glm.predict_interval(&design(&xs, 1000), 0.95).unwrap();
+        let covered = (0..1000)
+            .filter(|&i| {
+                let yi = 1. + 2. * xs[i] + ys[i];
+                intervals[i].0 < yi && yi < intervals[i].1
+            })
+            .count();
+        assert!(covered > 920 && covered < 980);
+
+        // intervals for logistic regression are on the probability scale
+        let x = vec![
+            0.50, 0.75, 1.00, 1.25, 1.50, 1.75, 1.75, 2.00, 2.25, 2.50, 2.75, 3.00, 3.25, 3.50,
+            4.00, 4.25, 4.50, 4.75, 5.00, 5.50,
+        ];
+        let y = vec![
+            0., 0., 0., 0., 0., 0., 1., 0., 1., 0., 1., 0., 1., 0., 1., 1., 1., 1., 1., 1.,
+        ];
+        let mut glm = GLM::new(ExponentialFamily::Bernoulli);
+        glm.fit(&design(&x, 20), &y, 25).unwrap();
+        let new_x = design(&[1., 3., 5.], 3);
+        let pred = glm.predict(&new_x).unwrap();
+        let intervals = glm.predict_interval(&new_x, 0.95).unwrap();
+        for i in 0..3 {
+            assert!(0. < intervals[i].0 && intervals[i].0 < pred[i]);
+            assert!(pred[i] < intervals[i].1 && intervals[i].1 < 1.);
+        }
+    }
+
+    #[test]
+    fn test_glm_fit_with_formula() {
+        let mut data = std::collections::HashMap::new();
+        data.insert(
+            "hours",
+            vec![
+                0.50, 0.75, 1.00, 1.25, 1.50, 1.75, 1.75, 2.00, 2.25, 2.50, 2.75, 3.00, 3.25, 3.50,
+                4.00, 4.25, 4.50, 4.75, 5.00, 5.50,
+            ],
+        );
+        data.insert(
+            "pass",
+            vec![
+                0., 0., 0., 0., 0., 0., 1., 0., 1., 0., 1., 0., 1., 0., 1., 1., 1., 1., 1., 1.,
+            ],
+        );
+        let formula = Formula::new("pass ~ hours", data);
+
+        let mut glm = GLM::new(ExponentialFamily::Bernoulli);
+        glm.fit_with_formula(&formula, 25).unwrap();
+        let coef = glm.coef().unwrap();
+        assert_approx_eq!(coef[0], -4.0777, 1e-3);
+        assert_approx_eq!(coef[1], 1.5046, 1e-3);
+    }
+
+    #[test]
+    fn test_glm_dispersion() {
+        use crate::distributions::{Distribution1D, Uniform};
+
+        alea::set_seed(5);
+        let n = 2000;
+        let x = Uniform::new(0., 10.).sample_n(n);
+        let noise = Normal::new(0., 1.5).sample_n(n);
+        let y = (0..n)
+            .map(|i| 3. - 0.5 * x[i] + noise[i])
+            .collect::<Vec<_>>();
+        let mut glm = GLM::new(ExponentialFamily::Gaussian);
+        assert!(glm.dispersion().is_err());
+        glm.fit(&design(&x, n), &y, 25).unwrap();
+        assert_approx_eq!(glm.dispersion().unwrap(), 2.25, 0.1);
+
+        // the standard errors are scaled by the dispersion
+        let cov = invert_matrix(glm.information_matrix.as_ref().unwrap());
+        let errors = glm.coef_standard_error().unwrap();
+        for i in 0..2 {
+            assert_approx_eq!(
+                errors[i],
+                (glm.dispersion().unwrap() * cov[i * 2 + i]).sqrt()
+            );
+        }
+
+        // no dispersion parameter for Bernoulli models
+        let x = vec![0.5, 1., 1.5, 2., 2.5, 3., 3.5, 4.];
+        let y = vec![0., 0., 1., 0., 1., 0., 1., 1.];
+        let mut glm = GLM::new(ExponentialFamily::Bernoulli);
+        glm.fit(&design(&x, 8), &y, 25).unwrap();
+        assert_eq!(glm.dispersion().unwrap(), 1.);
+    }
+
+    #[test]
+    fn test_glm_nearly_separable() {
+        // the classes only overlap at x = 10 and x = 11, and the outlier at x = 200 is fitted with
+        // a probability that rounds to exactly 1
+        let x_without = (1..=20).map(|i| i as f64).collect::<Vec<_>>();
+        let mut x = x_without.clone();
+        x[19] = 200.;
+        let mut y = vec![0.; 20];
+        y[9] = 1.;
+        for yi in y.iter_mut().skip(11) {
+            *yi = 1.;
+        }
+
+        let mut glm = GLM::new(ExponentialFamily::Bernoulli);
+        glm.fit(&design(&x, 20), &y, 50).unwrap();
+        let coef = glm.coef().unwrap();
+        assert!(coef.iter().all(|c| c.is_finite()));
+        assert!(glm.deviance().unwrap().is_finite());
+
+        // the outlier barely changes the fit to the other points
+        let mut glm_without = GLM::new(ExponentialFamily::Bernoulli);
+        glm_without.fit(&design(&x_without, 20), &y, 50).unwrap();
+        let coef_without = glm_without.coef().unwrap();
+        assert_approx_eq!(coef[0], coef_without[0], 1e-3);
+        assert_approx_eq!(coef[1], coef_without[1], 1e-3);
+        // the decision boundary is between the overlapping points
+        assert_approx_eq!(-coef[0] / coef[1], 10.5, 1e-3);
+
+        // only the means used while fitting are kept away from 0 and 1, not the predictions
+        let pred = glm.predict(&design(&[-200., 200.], 2)).unwrap();
+        assert!(pred[0] > 0. && pred[0] < f64::EPSILON);
+        assert_eq!(pred[1], 1.);
+    }
+
+    #[test]
+    fn test_glm_weights_match_duplicated_rows() {
+        let x = vec![0.5, 1., 1.5, 2., 2.5, 3., 3.5, 4.];
+        let y = vec![1., 0., 2., 3., 2., 5., 4., 8.];
+        let counts = vec![1., 3., 2., 1., 4., 2., 1., 3.];
+
+        let mut x_dup = vec![];
+        let mut y_dup = vec![];
+        for i in 0..x.len() {
+            for _ in 0..counts[i] as usize {
+                x_dup.push(x[i]);
+                y_dup.push(y[i]);
+            }
+        }
+
+        let mut weighted = GLM::new(ExponentialFamily::Poisson);
+        weighted.set_tolerance(1e-10).set_weights(&counts);
+        weighted.fit(&design(&x, x.len()), &y, 50).unwrap();
+
+        let mut duplicated = GLM::new(ExponentialFamily::Poisson);
+        duplicated.set_tolerance(1e-10);
+        duplicated
+            .fit(&design(&x_dup, x_dup.len()), &y_dup, 50)
+            .unwrap();
+
+        let coef_w = weighted.coef().unwrap();
+        let coef_d = duplicated.coef().unwrap();
+        for i in 0..2 {
+            assert_approx_eq!(coef_w[i], coef_d[i], 1e-8);
+        }
+        assert_approx_eq!(
+            weighted.deviance().unwrap(),
+            duplicated.deviance().unwrap(),
+            1e-8
+        );
+        assert_approx_eq!(weighted.aic().unwrap(), duplicated.aic().unwrap(), 1e-8);
+    }
+
+    #[test]
+    fn test_glm_gaussian_deviance() {
+        // the Gaussian deviance is the (weighted) residual sum of squares
+        let gaussian = ExponentialFamily::Gaussian;
+        assert_eq!(gaussian.deviance(&[1., 2., -1.], &[0., 0., 1.]), 9.);
+        assert_eq!(
+            gaussian.weighted_deviance(&[1., 2.], &[0., 0.], &[1., 3.]),
+            13.
+        );
+
+        let x = vec![1., 2., 3., 4., 5., 6.];
+        let y = vec![1.2, 1.9, 3.2, 3.8, 5.3, 5.7];
+        let xd = design(&x, x.len());
+        let mut glm = GLM::new(ExponentialFamily::Gaussian);
+        glm.fit(&xd, &y, 25).unwrap();
+        let rss = glm
+            .predict(&xd)
+            .unwrap()
+            .iter()
+            .zip(&y)
+            .map(|(mu, yi)| (yi - mu).powi(2))
+            .sum::<f64>();
+        assert_approx_eq!(glm.deviance().unwrap(), rss, 1e-10);
+        assert_approx_eq!(glm.dispersion().unwrap(), rss / 4., 1e-10);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_glm_serde_roundtrip() {
+        let x = vec![
+            0.50, 0.75, 1.00, 1.25, 1.50, 1.75, 1.75, 2.00, 2.25, 2.50, 2.75, 3.00, 3.25, 3.50,
+            4.00, 4.25, 4.50, 4.75, 5.00, 5.50,
+        ];
+        let y = vec![
+            0., 0., 0., 0., 0., 0., 1., 0., 1., 0., 1., 0., 1., 0., 1., 1., 1., 1., 1., 1.,
+        ];
+        let n = y.len();
+        let xd = design(&x, n);
+
+        let mut glm = GLM::new(ExponentialFamily::Bernoulli);
+        glm.fit(&xd, &y, 25).unwrap();
+
+        let json = serde_json::to_string(&glm).unwrap();
+        let loaded: GLM = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(glm.coef().unwrap(), loaded.coef().unwrap());
+        assert_eq!(glm.deviance().unwrap(), loaded.deviance().unwrap());
+        assert!(matches!(loaded.family, ExponentialFamily::Bernoulli));
+
+        let new_obs_design = design(&[1., 2., 3., 4., 5.], 5);
+        assert_eq!(
+            glm.predict(&new_obs_design).unwrap(),
+            loaded.predict(&new_obs_design).unwrap()
+        );
+    }
 }
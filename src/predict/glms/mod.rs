@@ -6,9 +6,9 @@
 //! This code is a translation of [py-glm](https://github.com/madrury/py-glm).
 
 mod families;
-// mod formula;
+mod formula;
 mod glm;
 
 pub use families::*;
-// pub use formula::*;
+pub use formula::*;
 pub use glm::*;
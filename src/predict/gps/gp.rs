@@ -0,0 +1,216 @@
+//! Gaussian process regression.
+
+use super::{Kernel, RBFKernel};
+use crate::linalg::{cholesky, cholesky_solve, dot, forward_substitution};
+
+/// Implements [Gaussian process regression](https://en.wikipedia.org/wiki/Kriging) on one
+/// dimensional inputs, with a radial basis function (squared exponential) kernel and independent
+/// Gaussian observation noise.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GaussianProcess {
+    /// Output variance of the kernel.
+    var: f64,
+    /// Length scale of the kernel.
+    length_scale: f64,
+    /// Variance of the observation noise.
+    noise: f64,
+    x_train: Option<Vec<f64>>,
+    /// Cholesky factor of the kernel matrix of the training data (including noise).
+    chol: Option<Vec<f64>>,
+    /// The kernel matrix (including noise) applied to the training targets, `K^-1 y`.
+    alpha: Option<Vec<f64>>,
+}
+
+impl GaussianProcess {
+    /// Create a new Gaussian process with output variance `var`, length scale `length_scale`, and
+    /// observation noise variance `noise`.
+    ///
+    /// # Errors
+    /// Panics if `var` or `length_scale` is not positive, or if `noise` is negative.
+    pub fn new(var: f64, length_scale: f64, noise: f64) -> Self {
+        assert!(var > 0., "output variance must be positive");
+        assert!(length_scale > 0., "length scale must be positive");
+        assert!(noise >= 0., "noise must be non-negative");
+        Self {
+            var,
+            length_scale,
+            noise,
+            x_train: None,
+            chol: None,
+            alpha: None,
+        }
+    }
+
+    /// Returns the output variance of the kernel.
+    pub fn var(&self) -> f64 {
+        self.var
+    }
+
+    /// Returns the length scale of the kernel.
+    pub fn length_scale(&self) -> f64 {
+        self.length_scale
+    }
+
+    /// Returns the variance of the observation noise.
+    pub fn noise(&self) -> f64 {
+        self.noise
+    }
+
+    /// Set the output variance of the kernel. This discards the fit, so the model must be fitted
+    /// again before making predictions.
+    pub fn set_var(&mut self, var: f64) -> &mut Self {
+        assert!(var > 0., "output variance must be positive");
+        self.var = var;
+        self.reset();
+        self
+    }
+
+    /// Set the length scale of the kernel. This discards the fit, so the model must be fitted
+    /// again before making predictions.
+    pub fn set_length_scale(&mut self, length_scale: f64) -> &mut Self {
+        assert!(length_scale > 0., "length scale must be positive");
+        self.length_scale = length_scale;
+        self.reset();
+        self
+    }
+
+    /// Set the variance of the observation noise. This discards the fit, so the model must be
+    /// fitted again before making predictions.
+    pub fn set_noise(&mut self, noise: f64) -> &mut Self {
+        assert!(noise >= 0., "noise must be non-negative");
+        self.noise = noise;
+        self.reset();
+        self
+    }
+
+    /// Discards the fitted training data, which depends on the kernel and noise parameters.
+    fn reset(&mut self) {
+        self.x_train = None;
+        self.chol = None;
+        self.alpha = None;
+    }
+
+    fn kernel(&self) -> RBFKernel {
+        RBFKernel::new(self.var, self.length_scale)
+    }
+
+    /// Fit the Gaussian process to the training data `(x, y)` by Cholesky factorizing the kernel
+    /// matrix. A small jitter is added to the diagonal for numerical stability, so that the
+    /// kernel matrix can be factorized even when there is no noise.
+    pub fn fit(&mut self, x: &[f64], y: &[f64]) -> &mut Self {
+        assert_eq!(x.len(), y.len(), "x and y must have the same length");
+        assert!(!x.is_empty(), "need at least one data point");
+        let n = x.len();
+        let kernel = self.kernel();
+
+        let mut k = vec![0.; n * n];
+        for i in 0..n {
+            for j in 0..n {
+                k[i * n + j] = kernel.forward(x[i], x[j]);
+            }
+            k[i * n + i] += self.noise + 1e-10 * self.var;
+        }
+        let l = cholesky(&k);
+        self.alpha = Some(cholesky_solve(&l, y));
+        self.chol = Some(l);
+        self.x_train = Some(x.to_vec());
+        self
+    }
+
+    /// Predict the posterior means and variances of the (noise-free) function at the points
+    /// `x_new`.
+    ///
+    /// # Errors
+    /// Panics if the model has not been fitted.
+    pub fn predict(&self, x_new: &[f64]) -> (Vec<f64>, Vec<f64>) {
+        let (x_train, l, alpha) = match (&self.x_train, &self.chol, &self.alpha) {
+            (Some(x), Some(l), Some(alpha)) => (x, l, alpha),
+            _ => panic!("model has not been fitted"),
+        };
+        let kernel = self.kernel();
+
+        x_new
+            .iter()
+            .map(|&xi| {
+                let k_star = x_train
+                    .iter()
+                    .map(|&xj| kernel.forward(xi, xj))
+                    .collect::<Vec<_>>();
+                let mean = dot(&k_star, alpha);
+                let v = forward_substitution(l, &k_star);
+                let var = (kernel.forward(xi, xi) - dot(&v, &v)).max(0.);
+                (mean, var)
+            })
+            .unzip()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_gaussian_process() {
+        let x = (0..10).map(|i| i as f64 * 0.6).collect::<Vec<_>>();
+        let y = x.iter().map(|x| x.sin()).collect::<Vec<_>>();
+        let mut gp = GaussianProcess::new(1., 1., 0.);
+        gp.fit(&x, &y);
+
+        let (mean, var) = gp.predict(&x);
+        for i in 0..x.len() {
+            assert!((mean[i] - y[i]).abs() < 1e-4);
+            assert!(var[i] < 1e-6);
+        }
+
+        // smooth interpolation between the training points
+        let (mean, var) = gp.predict(&[0.9, 2.7, 4.5]);
+        for (m, xi) in mean.iter().zip(&[0.9_f64, 2.7, 4.5]) {
+            assert!((m - xi.sin()).abs() < 1e-2);
+        }
+        // the variance grows away from the data, up to the prior variance
+        let (_, far) = gp.predict(&[7., 10., 100.]);
+        assert!(var.iter().all(|v| *v < far[0]));
+        assert!(far[0] < far[1] && far[1] < far[2]);
+        assert_approx_eq!(far[2], 1.);
+
+        // with noise the training points are smoothed rather than interpolated
+        gp.set_noise(0.1).set_length_scale(1.5).fit(&x, &y);
+        let (mean, var) = gp.predict(&x);
+        assert!(mean.iter().zip(&y).any(|(m, y)| (m - y).abs() > 1e-3));
+        assert!(var.iter().all(|v| *v > 1e-4));
+    }
+
+    #[test]
+    #[should_panic(expected = "model has not been fitted")]
+    fn test_gaussian_process_unfitted() {
+        GaussianProcess::new(1., 1., 0.).predict(&[1.]);
+    }
+
+    #[test]
+    fn test_gaussian_process_refit_after_setters() {
+        let x = [0., 1., 2.5];
+        let y = [0.3, -0.2, 1.1];
+        let mut gp = GaussianProcess::new(2., 1., 0.1);
+        gp.fit(&x, &y);
+
+        // refitting with the new parameters gives the same predictions as a fresh model
+        gp.set_var(1.5)
+            .set_length_scale(0.5)
+            .set_noise(0.2)
+            .fit(&x, &y);
+        assert_eq!((gp.var(), gp.length_scale(), gp.noise()), (1.5, 0.5, 0.2));
+        let mut fresh = GaussianProcess::new(1.5, 0.5, 0.2);
+        fresh.fit(&x, &y);
+        assert_eq!(gp.predict(&[0.5, 3.]), fresh.predict(&[0.5, 3.]));
+    }
+
+    #[test]
+    #[should_panic(expected = "model has not been fitted")]
+    fn test_gaussian_process_setter_discards_fit() {
+        let mut gp = GaussianProcess::new(1., 1., 0.);
+        gp.fit(&[0., 1.], &[1., 2.]).set_length_scale(2.);
+        gp.predict(&[0.5]);
+    }
+}
@@ -1,5 +1,7 @@
 //! Tools for dealing with [Gaussian processes](https://en.wikipedia.org/wiki/Gaussian_process#Usual_covariance_functions).
 
+mod gp;
 pub mod kernels;
 
+pub use self::gp::*;
 pub use self::kernels::*;
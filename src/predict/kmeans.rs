@@ -0,0 +1,176 @@
+//! K-means clustering.
+
+use crate::distributions::{cumulative_weights, draw_index};
+use crate::linalg::euclidean_distance;
+
+/// Maximum number of iterations of Lloyd's algorithm.
+const MAX_ITER: usize = 300;
+
+/// Implements [k-means clustering](https://en.wikipedia.org/wiki/K-means_clustering) using Lloyd's
+/// algorithm, with centroids initialized by [k-means++](https://en.wikipedia.org/wiki/K-means%2B%2B).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KMeans {
+    /// Number of clusters.
+    pub k: usize,
+    /// Random seed used for the k-means++ initialization.
+    pub seed: u64,
+    /// Row-major `k x ncols` matrix of cluster centroids.
+    centroids: Option<Vec<f64>>,
+    ncols: usize,
+}
+
+impl KMeans {
+    /// Create a new k-means model with `k` clusters, using the random seed `seed` to initialize
+    /// the centroids.
+    ///
+    /// # Errors
+    /// Panics if `k` is zero.
+    pub fn new(k: usize, seed: u64) -> Self {
+        assert!(k > 0, "k must be positive");
+        Self {
+            k,
+            seed,
+            centroids: None,
+            ncols: 0,
+        }
+    }
+
+    /// Fit the model to the `nrows x ncols` row-major matrix `data`, where each row is an
+    /// observation. Iteration stops once the cluster assignments no longer change.
+    ///
+    /// # Errors
+    /// Panics if the shape of `data` does not match `nrows` and `ncols`, or if there are fewer
+    /// observations than clusters.
+    pub fn fit(&mut self, data: &[f64], nrows: usize, ncols: usize) -> &mut Self {
+        assert_eq!(data.len(), nrows * ncols, "matrix shape mismatch");
+        assert!(
+            nrows >= self.k,
+            "need at least as many observations as clusters"
+        );
+        let rows = data.chunks_exact(ncols).collect::<Vec<_>>();
+
+        let mut centroids = self.init_centroids(&rows);
+        let mut labels = vec![usize::MAX; nrows];
+        for _ in 0..MAX_ITER {
+            let mut changed = false;
+            for (label, row) in labels.iter_mut().zip(&rows) {
+                let nearest = nearest(&centroids, row);
+                if nearest != *label {
+                    *label = nearest;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+
+            let mut sums = vec![0.; self.k * ncols];
+            let mut counts = vec![0; self.k];
+            for (&label, row) in labels.iter().zip(&rows) {
+                counts[label] += 1;
+                sums[label * ncols..(label + 1) * ncols]
+                    .iter_mut()
+                    .zip(row.iter())
+                    .for_each(|(s, x)| *s += x);
+            }
+            // empty clusters keep their previous centroid
+            for (c, &count) in counts.iter().enumerate() {
+                if count > 0 {
+                    for j in 0..ncols {
+                        centroids[c][j] = sums[c * ncols + j] / count as f64;
+                    }
+                }
+            }
+        }
+
+        self.centroids = Some(centroids.concat());
+        self.ncols = ncols;
+        self
+    }
+
+    /// Return the index of the cluster whose centroid is closest to `row`.
+    pub fn predict(&self, row: &[f64]) -> usize {
+        let centroids = self.centroids();
+        assert_eq!(row.len(), self.ncols, "row has the wrong number of columns");
+        nearest(&centroids.chunks_exact(self.ncols).collect::<Vec<_>>(), row)
+    }
+
+    /// Return the fitted centroids as a row-major `k x ncols` matrix.
+    pub fn centroids(&self) -> &[f64] {
+        self.centroids.as_ref().expect("model has not been fitted")
+    }
+
+    /// Choose the initial centroids with k-means++: the first uniformly at random, and each
+    /// subsequent one with probability proportional to its squared distance from the nearest
+    /// centroid already chosen.
+    fn init_centroids(&self, rows: &[&[f64]]) -> Vec<Vec<f64>> {
+        alea::set_seed(self.seed);
+        let mut centroids = vec![rows[alea::u64_less_than(rows.len() as u64) as usize].to_vec()];
+        let mut dists = rows
+            .iter()
+            .map(|row| euclidean_distance(&centroids[0], row).powi(2))
+            .collect::<Vec<_>>();
+        while centroids.len() < self.k {
+            let total = dists.iter().sum::<f64>();
+            let next = if total > 0. {
+                draw_index(&cumulative_weights(&dists))
+            } else {
+                // every point coincides with a centroid
+                alea::u64_less_than(rows.len() as u64) as usize
+            };
+            centroids.push(rows[next].to_vec());
+            for (d, row) in dists.iter_mut().zip(rows) {
+                *d = d.min(euclidean_distance(&centroids[centroids.len() - 1], row).powi(2));
+            }
+        }
+        centroids
+    }
+}
+
+/// Index of the centroid closest to `row`.
+fn nearest<C: AsRef<[f64]>>(centroids: &[C], row: &[f64]) -> usize {
+    let mut best = 0;
+    let mut best_dist = f64::INFINITY;
+    for (i, c) in centroids.iter().enumerate() {
+        let d = euclidean_distance(c.as_ref(), row);
+        if d < best_dist {
+            best = i;
+            best_dist = d;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{Distribution1D, Normal};
+
+    #[test]
+    fn test_kmeans() {
+        alea::set_seed(3);
+        let n = 100;
+        let means = [[-2., 5.], [6., 1.]];
+        let noise = Normal::new(0., 1.).sample_n(2 * n * 2);
+        let data = (0..2 * n)
+            .flat_map(|i| {
+                let m = means[i / n];
+                vec![m[0] + noise[2 * i], m[1] + noise[2 * i + 1]]
+            })
+            .collect::<Vec<_>>();
+
+        let mut km = KMeans::new(2, 42);
+        km.fit(&data, 2 * n, 2);
+        let centroids = km.centroids();
+        for m in &means {
+            let c = km.predict(m);
+            assert!(euclidean_distance(&centroids[2 * c..2 * c + 2], m) < 0.3);
+        }
+        assert_ne!(km.predict(&means[0]), km.predict(&means[1]));
+
+        // the same seed gives the same clustering
+        let mut km2 = KMeans::new(2, 42);
+        assert_eq!(km2.fit(&data, 2 * n, 2).centroids(), centroids);
+    }
+}
@@ -1,8 +1,12 @@
 //! Various statistical models for data fitting and prediction.
 
+mod diagnostics;
 mod glms;
 mod gps;
+mod kmeans;
+mod penalized;
 mod polynomial;
+mod preprocessing;
 // use crate::optimize::optimizers::Optimizer;
 
 // /// A predictor for which the parameters can be optimized and updated.
@@ -20,6 +24,10 @@ mod polynomial;
 //     fn predict(&self, x: &[f64]) -> Vec<f64>;
 // }
 
+pub use self::diagnostics::*;
 pub use self::glms::*;
 pub use self::gps::*;
+pub use self::kmeans::*;
+pub use self::penalized::*;
 pub use self::polynomial::*;
+pub use self::preprocessing::*;
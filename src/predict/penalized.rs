@@ -0,0 +1,290 @@
+//! Penalized linear regression models.
+
+use crate::linalg::{dot, matmul, solve};
+use crate::statistics::mean;
+
+/// Returns whether the first column of the `nrows x ncols` row-major matrix `x` is all ones, in
+/// which case it is treated as an intercept.
+fn has_intercept(x: &[f64], nrows: usize, ncols: usize) -> bool {
+    ncols > 0 && (0..nrows).all(|i| x[i * ncols] == 1.)
+}
+
+/// Fits a [ridge regression](https://en.wikipedia.org/wiki/Ridge_regression) in closed form by
+/// solving `(X^T X + alpha I) b = X^T y`, where `x` is an `nrows x ncols` row-major matrix. If the
+/// first column of `x` is all ones (e.g., from [design](crate::linalg::design)), it is treated as
+/// an intercept and its coefficient is not penalized. With `alpha = 0`, this is ordinary least
+/// squares.
+///
+/// # Errors
+/// Panics if `alpha` is negative, or if the shapes of `x` and `y` do not match `nrows` and `ncols`.
+pub fn ridge(x: &[f64], y: &[f64], alpha: f64, nrows: usize, ncols: usize) -> Vec<f64> {
+    assert!(alpha >= 0., "alpha must be non-negative");
+    assert_eq!(x.len(), nrows * ncols, "matrix shape mismatch");
+    assert_eq!(y.len(), nrows, "y must have length nrows");
+
+    let mut xtx = matmul(x, x, nrows, nrows, true, false);
+    let start = if has_intercept(x, nrows, ncols) { 1 } else { 0 };
+    for j in start..ncols {
+        xtx[j * ncols + j] += alpha;
+    }
+    let xty = matmul(x, y, nrows, nrows, true, false);
+    solve(&xtx, &xty)
+}
+
+/// Fits a [lasso regression](https://en.wikipedia.org/wiki/Lasso_(statistics)) using cyclic
+/// coordinate descent with soft-thresholding, where `x` is an `nrows x ncols` row-major matrix.
+/// The objective minimized is `||y - X b||^2 / 2 + alpha ||b||_1`, with the penalty applied to
+/// the coefficients of the standardized predictors; the returned coefficients are on the original
+/// scale. If the first column of `x` is all ones, it is treated as an unpenalized intercept.
+/// Iteration stops after `max_iter` sweeps over the coefficients or once they stop changing.
+///
+/// # Errors
+/// Panics if `alpha` is negative, or if the shapes of `x` and `y` do not match `nrows` and `ncols`.
+pub fn lasso(
+    x: &[f64],
+    y: &[f64],
+    alpha: f64,
+    max_iter: usize,
+    nrows: usize,
+    ncols: usize,
+) -> Vec<f64> {
+    assert!(alpha >= 0., "alpha must be non-negative");
+    coordinate_descent(x, y, alpha, 0., max_iter, nrows, ncols)
+}
+
+/// Fits an [elastic net](https://en.wikipedia.org/wiki/Elastic_net_regularization) regression
+/// using cyclic coordinate descent, where `x` is an `nrows x ncols` row-major matrix. The objective
+/// minimized is `||y - X b||^2 / 2 + alpha (l1_ratio ||b||_1 + (1 - l1_ratio) ||b||^2 / 2)`, with
+/// the penalty applied to the coefficients of the standardized predictors as in [lasso]; the
+/// returned coefficients are on the original scale. With `l1_ratio = 1` this is the lasso, and
+/// with `l1_ratio = 0` it is [ridge] regression on the standardized predictors. If the first column
+/// of `x` is all ones, it is treated as an unpenalized intercept.
+///
+/// # Errors
+/// Panics if `alpha` is negative, if `l1_ratio` is not between 0 and 1, or if the shapes of `x`
+/// and `y` do not match `nrows` and `ncols`.
+pub fn elastic_net(
+    x: &[f64],
+    y: &[f64],
+    alpha: f64,
+    l1_ratio: f64,
+    max_iter: usize,
+    nrows: usize,
+    ncols: usize,
+) -> Vec<f64> {
+    assert!(alpha >= 0., "alpha must be non-negative");
+    assert!(
+        (0. ..=1.).contains(&l1_ratio),
+        "l1_ratio must be between 0 and 1"
+    );
+    coordinate_descent(
+        x,
+        y,
+        alpha * l1_ratio,
+        alpha * (1. - l1_ratio),
+        max_iter,
+        nrows,
+        ncols,
+    )
+}
+
+/// Tolerance on the largest coefficient update in a sweep of coordinate descent.
+const CD_TOL: f64 = 1e-10;
+
+/// Minimizes `||y - X b||^2 / 2 + l1 ||b||_1 + l2 ||b||^2 / 2` over the coefficients of the
+/// standardized predictors by cyclic coordinate descent, returning coefficients on the original
+/// scale. Predictors are centered only if there is an intercept column.
+fn coordinate_descent(
+    x: &[f64],
+    y: &[f64],
+    l1: f64,
+    l2: f64,
+    max_iter: usize,
+    nrows: usize,
+    ncols: usize,
+) -> Vec<f64> {
+    assert_eq!(x.len(), nrows * ncols, "matrix shape mismatch");
+    assert_eq!(y.len(), nrows, "y must have length nrows");
+
+    let intercept = has_intercept(x, nrows, ncols);
+    let n = nrows as f64;
+    let ybar = if intercept { mean(y) } else { 0. };
+
+    // standardize the predictors, storing them column-major for the sweeps
+    let mut centers = vec![0.; ncols];
+    let mut scales = vec![1.; ncols];
+    let mut z = vec![0.; nrows * ncols];
+    for j in 0..ncols {
+        if intercept && j == 0 {
+            continue;
+        }
+        let col = &mut z[j * nrows..(j + 1) * nrows];
+        for (i, zij) in col.iter_mut().enumerate() {
+            *zij = x[i * ncols + j];
+        }
+        if intercept {
+            centers[j] = mean(col);
+        }
+        let scale = (col.iter().map(|v| (v - centers[j]).powi(2)).sum::<f64>() / n).sqrt();
+        if scale > 0. {
+            scales[j] = scale;
+        }
+        col.iter_mut()
+            .for_each(|v| *v = (*v - centers[j]) / scales[j]);
+    }
+
+    let mut coef = vec![0.; ncols];
+    let mut resid = y.iter().map(|v| v - ybar).collect::<Vec<_>>();
+    for _ in 0..max_iter {
+        let mut max_step = 0_f64;
+        for j in 0..ncols {
+            let col = &z[j * nrows..(j + 1) * nrows];
+            let sq = dot(col, col);
+            if sq == 0. {
+                continue;
+            }
+            let rho = dot(col, &resid) + sq * coef[j];
+            let new = soft_threshold(rho, l1) / (sq + l2);
+            let step = new - coef[j];
+            if step != 0. {
+                resid
+                    .iter_mut()
+                    .zip(col)
+                    .for_each(|(r, zij)| *r -= step * zij);
+                coef[j] = new;
+                max_step = max_step.max(step.abs());
+            }
+        }
+        if max_step < CD_TOL {
+            break;
+        }
+    }
+
+    // map back to the original scale
+    for j in 0..ncols {
+        coef[j] /= scales[j];
+    }
+    if intercept {
+        coef[0] = ybar - dot(&coef, &centers);
+    }
+    coef
+}
+
+fn soft_threshold(x: f64, threshold: f64) -> f64 {
+    x.signum() * (x.abs() - threshold).max(0.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{Distribution1D, Normal};
+    use crate::linalg::norm;
+    use crate::predict::StandardScaler;
+    use approx_eq::assert_approx_eq;
+
+    /// Design matrix with an intercept and `p` standard normal features.
+    fn features(n: usize, p: usize) -> Vec<f64> {
+        let z = Normal::new(0., 1.).sample_n(n * p);
+        (0..n)
+            .flat_map(|i| {
+                let row = z[i * p..(i + 1) * p].to_vec();
+                std::iter::once(1.).chain(row)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ridge() {
+        alea::set_seed(4);
+        let n = 100;
+        let x = features(n, 2);
+        let noise = Normal::new(0., 0.1).sample_n(n);
+        let y = (0..n)
+            .map(|i| 1. + 2. * x[i * 3 + 1] - 3. * x[i * 3 + 2] + noise[i])
+            .collect::<Vec<_>>();
+
+        // alpha = 0 solves the normal equations
+        let ols = ridge(&x, &y, 0., n, 3);
+        let xtx = matmul(&x, &x, n, n, true, false);
+        let xty = matmul(&x, &y, n, n, true, false);
+        let gradient = matmul(&xtx, &ols, 3, 3, false, false);
+        for j in 0..3 {
+            assert_approx_eq!(gradient[j], xty[j], 1e-8);
+        }
+        assert!((ols[1] - 2.).abs() < 0.05 && (ols[2] + 3.).abs() < 0.05);
+
+        // increasing alpha shrinks the slopes but not the intercept
+        let mut prev = norm(&ols[1..]);
+        for &alpha in &[1., 10., 100., 1e4] {
+            let coef = ridge(&x, &y, alpha, n, 3);
+            let size = norm(&coef[1..]);
+            assert!(size < prev);
+            prev = size;
+        }
+        let coef = ridge(&x, &y, 1e8, n, 3);
+        assert!(norm(&coef[1..]) < 1e-3);
+        assert!((coef[0] - mean(&y)).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_lasso() {
+        alea::set_seed(7);
+        let n = 200;
+        let p = 8;
+        let x = features(n, p);
+        let noise = Normal::new(0., 0.5).sample_n(n);
+        let y = (0..n)
+            .map(|i| 1. + 3. * x[i * (p + 1) + 1] - 2. * x[i * (p + 1) + 2] + noise[i])
+            .collect::<Vec<_>>();
+
+        let coef = lasso(&x, &y, 40., 1000, n, p + 1);
+        assert!((coef[0] - 1.).abs() < 0.2);
+        assert!(coef[1] > 2.5 && coef[2] < -1.5);
+        assert!(coef[3..].iter().all(|&b| b == 0.));
+
+        // without a penalty, lasso is least squares
+        let ols = ridge(&x, &y, 0., n, p + 1);
+        let coef = lasso(&x, &y, 0., 1000, n, p + 1);
+        for j in 0..=p {
+            assert_approx_eq!(coef[j], ols[j], 1e-6);
+        }
+
+        // a large enough penalty removes every predictor
+        let coef = lasso(&x, &y, 1e4, 1000, n, p + 1);
+        assert_approx_eq!(coef[0], mean(&y));
+        assert!(coef[1..].iter().all(|&b| b == 0.));
+    }
+
+    #[test]
+    fn test_elastic_net() {
+        alea::set_seed(11);
+        let n = 150;
+        let p = 5;
+        let raw = Normal::new(2., 3.).sample_n(n * p);
+        let y = (0..n)
+            .map(|i| 4. - raw[i * p] + 0.5 * raw[i * p + 2])
+            .collect::<Vec<_>>();
+
+        // standardize the predictors so that the ridge penalty matches
+        let mut scaler = StandardScaler::new();
+        let z = scaler.fit(&raw, n, p).transform(&raw);
+        let x = (0..n)
+            .flat_map(|i| std::iter::once(1.).chain(z[i * p..(i + 1) * p].to_vec()))
+            .collect::<Vec<_>>();
+
+        let l1 = elastic_net(&x, &y, 30., 1., 1000, n, p + 1);
+        assert_eq!(l1, lasso(&x, &y, 30., 1000, n, p + 1));
+
+        let l2 = elastic_net(&x, &y, 30., 0., 1000, n, p + 1);
+        let closed = ridge(&x, &y, 30., n, p + 1);
+        for j in 0..=p {
+            assert_approx_eq!(l2[j], closed[j], 1e-8);
+        }
+
+        // any amount of L1 penalty can set coefficients exactly to zero
+        let mixed = elastic_net(&x, &y, 30., 0.5, 1000, n, p + 1);
+        assert!(l2.iter().all(|&b| b != 0.));
+        assert_eq!([mixed[2], mixed[4], mixed[5]], [0.; 3]);
+        assert!(mixed[1] < -2. && mixed[3] > 1.);
+    }
+}
@@ -0,0 +1,176 @@
+//! Preprocessing of design matrices.
+
+use crate::statistics::{mean, std};
+
+/// Standardizes the columns of a design matrix to have zero mean and unit variance, remembering
+/// the column means and standard deviations so that the transformation can be undone.
+///
+/// # Remarks
+/// Columns with zero variance (such as a column of ones for the intercept) are only centered, not
+/// scaled.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StandardScaler {
+    pub means: Vec<f64>,
+    pub stds: Vec<f64>,
+}
+
+impl StandardScaler {
+    /// Create a new, unfitted scaler.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compute and store the mean and (population) standard deviation of each column of the
+    /// `nrows x ncols` row-major matrix `data`.
+    pub fn fit(&mut self, data: &[f64], nrows: usize, ncols: usize) -> &mut Self {
+        assert_eq!(data.len(), nrows * ncols, "matrix shape mismatch");
+        let cols = (0..ncols)
+            .map(|j| (0..nrows).map(|i| data[i * ncols + j]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        self.means = cols.iter().map(|c| mean(c)).collect();
+        self.stds = cols
+            .iter()
+            .map(|c| {
+                let s = std(c);
+                if s > 0. {
+                    s
+                } else {
+                    1.
+                }
+            })
+            .collect();
+        self
+    }
+
+    /// Standardize the columns of the row-major matrix `data` using the fitted means and standard
+    /// deviations.
+    pub fn transform(&self, data: &[f64]) -> Vec<f64> {
+        let ncols = self.ncols(data);
+        data.iter()
+            .enumerate()
+            .map(|(i, x)| (x - self.means[i % ncols]) / self.stds[i % ncols])
+            .collect()
+    }
+
+    /// Undo the standardization of the columns of the row-major matrix `data`, mapping it back to
+    /// the original scale.
+    pub fn inverse_transform(&self, data: &[f64]) -> Vec<f64> {
+        let ncols = self.ncols(data);
+        data.iter()
+            .enumerate()
+            .map(|(i, x)| x * self.stds[i % ncols] + self.means[i % ncols])
+            .collect()
+    }
+
+    fn ncols(&self, data: &[f64]) -> usize {
+        let ncols = self.means.len();
+        assert!(ncols > 0, "scaler has not been fitted");
+        assert_eq!(
+            data.len() % ncols,
+            0,
+            "data does not have the number of columns the scaler was fitted on"
+        );
+        ncols
+    }
+}
+
+/// One-hot encodes categorical labels in `0..n_categories`. Returns a row-major
+/// `labels.len() x n_categories` matrix of dummy variables, where the `i`th row has a one in the
+/// column given by `labels[i]` and zeros elsewhere.
+///
+/// # Errors
+/// Panics if any label is not less than `n_categories`.
+pub fn one_hot(labels: &[usize], n_categories: usize) -> Vec<f64> {
+    let mut encoded = vec![0.; labels.len() * n_categories];
+    for (i, &label) in labels.iter().enumerate() {
+        assert!(label < n_categories, "label out of range");
+        encoded[i * n_categories + label] = 1.;
+    }
+    encoded
+}
+
+/// One-hot encodes categorical labels in `0..n_categories`, dropping the column for the first
+/// category. Returns a row-major `labels.len() x (n_categories - 1)` matrix, where rows with label
+/// 0 are all zeros. This avoids perfect collinearity with the intercept in regression models.
+///
+/// # Errors
+/// Panics if `n_categories` is zero or if any label is not less than `n_categories`.
+pub fn one_hot_drop_first(labels: &[usize], n_categories: usize) -> Vec<f64> {
+    assert!(n_categories > 0, "need at least one category");
+    let ncols = n_categories - 1;
+    let mut encoded = vec![0.; labels.len() * ncols];
+    for (i, &label) in labels.iter().enumerate() {
+        assert!(label < n_categories, "label out of range");
+        if label > 0 {
+            encoded[i * ncols + label - 1] = 1.;
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_standard_scaler() {
+        let (nrows, ncols) = (5, 3);
+        let data = vec![
+            1., 120., 0.5, 1., 98., 0.7, 1., 143., 0.1, 1., 110., 0.4, 1., 131., 0.9,
+        ];
+        let mut scaler = StandardScaler::new();
+        scaler.fit(&data, nrows, ncols);
+        let transformed = scaler.transform(&data);
+
+        for j in 1..ncols {
+            let col = (0..nrows)
+                .map(|i| transformed[i * ncols + j])
+                .collect::<Vec<_>>();
+            assert!(mean(&col).abs() < 1e-12);
+            assert_approx_eq!(std(&col), 1.);
+        }
+        // the constant column is centered but not scaled
+        assert!((0..nrows).all(|i| transformed[i * ncols] == 0.));
+
+        let recovered = scaler.inverse_transform(&transformed);
+        for i in 0..data.len() {
+            assert_approx_eq!(recovered[i], data[i]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "scaler has not been fitted")]
+    fn test_standard_scaler_unfitted() {
+        StandardScaler::new().transform(&[1., 2.]);
+    }
+
+    #[test]
+    fn test_one_hot() {
+        let labels = [2, 0, 1, 2, 3];
+        let encoded = one_hot(&labels, 4);
+        assert_eq!(encoded.len(), 5 * 4);
+        for (row, &label) in encoded.chunks(4).zip(&labels) {
+            assert_eq!(row.iter().sum::<f64>(), 1.);
+            assert_eq!(row[label], 1.);
+        }
+
+        let encoded = one_hot_drop_first(&labels, 4);
+        assert_eq!(encoded.len(), 5 * 3);
+        for (row, &label) in encoded.chunks(3).zip(&labels) {
+            if label == 0 {
+                assert!(row.iter().all(|&x| x == 0.));
+            } else {
+                assert_eq!(row.iter().sum::<f64>(), 1.);
+                assert_eq!(row[label - 1], 1.);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "label out of range")]
+    fn test_one_hot_out_of_range() {
+        one_hot(&[0, 3], 3);
+    }
+}
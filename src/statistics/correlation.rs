@@ -0,0 +1,155 @@
+use crate::statistics::{covariance, rankdata, std};
+
+/// Calculates the [Pearson correlation
+/// coefficient](https://en.wikipedia.org/wiki/Pearson_correlation_coefficient) between two vectors
+/// x and y, which measures the strength of a linear relationship between them.
+pub fn pearson(x: &[f64], y: &[f64]) -> f64 {
+    assert_eq!(x.len(), y.len(), "x and y must have the same length");
+    covariance(x, y) / (std(x) * std(y))
+}
+
+/// Calculates [Spearman's rank correlation
+/// coefficient](https://en.wikipedia.org/wiki/Spearman%27s_rank_correlation_coefficient) between
+/// two vectors x and y, which is the Pearson correlation between their ranks. It measures the
+/// strength of a monotonic relationship between them. Tied values are given their average rank.
+pub fn spearman(x: &[f64], y: &[f64]) -> f64 {
+    assert_eq!(x.len(), y.len(), "x and y must have the same length");
+    pearson(&rankdata(x), &rankdata(y))
+}
+
+/// Calculates [Kendall's rank correlation
+/// coefficient](https://en.wikipedia.org/wiki/Kendall_rank_correlation_coefficient) between two
+/// vectors x and y, from the numbers of concordant and discordant pairs. Ties are accounted for
+/// using the tau-b variant, as in scipy.
+pub fn kendall(x: &[f64], y: &[f64]) -> f64 {
+    assert_eq!(x.len(), y.len(), "x and y must have the same length");
+    let n = x.len();
+    let mut concordant_minus_discordant = 0.;
+    let mut untied_x = 0.;
+    let mut untied_y = 0.;
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let dx = sign(x[i] - x[j]);
+            let dy = sign(y[i] - y[j]);
+            concordant_minus_discordant += dx * dy;
+            untied_x += dx.abs();
+            untied_y += dy.abs();
+        }
+    }
+    concordant_minus_discordant / (untied_x * untied_y).sqrt()
+}
+
+/// Returns the sign of `d`, which is 0 for `d == 0` (unlike [f64::signum]).
+fn sign(d: f64) -> f64 {
+    if d > 0. {
+        1.
+    } else if d < 0. {
+        -1.
+    } else {
+        0.
+    }
+}
+
+/// The correlation coefficient used by [corr_matrix_method].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorrMethod {
+    /// The Pearson correlation coefficient; see [pearson].
+    Pearson,
+    /// Spearman's rank correlation coefficient; see [spearman].
+    Spearman,
+    /// Kendall's rank correlation coefficient; see [kendall].
+    Kendall,
+}
+
+/// Calculates the (Pearson) correlation matrix of the columns of the `nrows x ncols` row-major
+/// matrix `data`. See [corr_matrix_method] to use a rank correlation instead.
+pub fn corr_matrix(data: &[f64], nrows: usize, ncols: usize) -> Vec<f64> {
+    corr_matrix_method(data, nrows, ncols, CorrMethod::Pearson)
+}
+
+/// Calculates the `ncols x ncols` matrix of the correlations between each pair of columns of the
+/// `nrows x ncols` row-major matrix `data`, using the correlation coefficient given by `method`.
+///
+/// # Errors
+/// Panics if the length of `data` is not `nrows * ncols`.
+pub fn corr_matrix_method(
+    data: &[f64],
+    nrows: usize,
+    ncols: usize,
+    method: CorrMethod,
+) -> Vec<f64> {
+    assert_eq!(data.len(), nrows * ncols, "matrix shape mismatch");
+    let columns = (0..ncols)
+        .map(|j| (0..nrows).map(|i| data[i * ncols + j]).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let corr = match method {
+        CorrMethod::Pearson => pearson,
+        CorrMethod::Spearman => spearman,
+        CorrMethod::Kendall => kendall,
+    };
+
+    let mut matrix = vec![1.; ncols * ncols];
+    for i in 0..ncols {
+        for j in (i + 1)..ncols {
+            let r = corr(&columns[i], &columns[j]);
+            matrix[i * ncols + j] = r;
+            matrix[j * ncols + i] = r;
+        }
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_correlations() {
+        let x = [1.1, 1.7, 2.1, 1.4, 0.2];
+        let y = [3.0, 4.2, 4.9, 4.1, 2.5];
+        assert_approx_eq!(pearson(&x, &y), 0.9542500347004005);
+        assert_approx_eq!(spearman(&x, &y), 1.);
+        assert_approx_eq!(kendall(&x, &y), 1.);
+
+        // with one tie in each vector, 9 more concordant than discordant pairs out of the 14
+        // untied pairs in each
+        let x = [1., 2., 2., 3., 4., 5.];
+        let y = [2., 1., 3., 3., 6., 5.];
+        assert_approx_eq!(kendall(&x, &y), 9. / 14.);
+        assert_approx_eq!(spearman(&x, &y), 0.8088235294117647);
+    }
+
+    #[test]
+    fn test_corr_matrix_method() {
+        // monotonic but nonlinear relationships between the columns
+        let n = 20;
+        let data = (0..n)
+            .flat_map(|i| {
+                let x = i as f64 / 4.;
+                vec![x, x.exp(), -x.powi(3)]
+            })
+            .collect::<Vec<_>>();
+
+        let pearson = corr_matrix(&data, n, 3);
+        let spearman = corr_matrix_method(&data, n, 3, CorrMethod::Spearman);
+        let kendall = corr_matrix_method(&data, n, 3, CorrMethod::Kendall);
+        assert_eq!(
+            pearson,
+            corr_matrix_method(&data, n, 3, CorrMethod::Pearson)
+        );
+
+        for i in 0..3 {
+            assert_eq!(spearman[i * 3 + i], 1.);
+            for j in 0..3 {
+                assert_eq!(spearman[i * 3 + j], spearman[j * 3 + i]);
+                assert_approx_eq!(spearman[i * 3 + j].abs(), 1.);
+                assert_approx_eq!(kendall[i * 3 + j].abs(), 1.);
+                if i != j {
+                    assert!(pearson[i * 3 + j].abs() < spearman[i * 3 + j].abs());
+                }
+            }
+        }
+        assert_approx_eq!(spearman[2], -1.);
+    }
+}
@@ -1,11 +1,17 @@
 //! A module for computing statistics of data.
 
+mod correlation;
 mod covariance;
 mod moments;
 mod order;
-// mod tests;
+mod streaming_quantile;
+mod summary;
+mod tests;
 
+pub use self::correlation::*;
 pub use self::covariance::*;
 pub use self::moments::*;
 pub use self::order::*;
-// pub use self::tests::*;
+pub use self::streaming_quantile::*;
+pub use self::summary::*;
+pub use self::tests::*;
@@ -44,18 +44,31 @@ pub fn welford_mean(data: &[f64]) -> f64 {
     mean
 }
 
+/// Calculates the variance from an array of data points in a numerically stable manner using the
+/// Welford algorithm, with `ddof` delta degrees of freedom. The divisor used is `n - ddof`, so
+/// `ddof = 0` gives the population variance and `ddof = 1` gives the sample variance. This
+/// matches the `ddof` argument in numpy.
+pub fn var_ddof(data: &[f64], ddof: usize) -> f64 {
+    let (count, _, m2) = welford_statistics(data);
+    m2 / (count as f64 - ddof as f64)
+}
+
 /// Calculates the population variance from an array of data points in a numerically stable manner
 /// using the Welford algorithm.
 pub fn var(data: &[f64]) -> f64 {
-    let (count, _, m2) = welford_statistics(data);
-    m2 / count as f64
+    var_ddof(data, 0)
 }
 
 /// Calculates the sample variance from an array of data points in a numerically stable manner
 /// using the Welford algorithm.
 pub fn sample_var(data: &[f64]) -> f64 {
-    let (count, _, m2) = welford_statistics(data);
-    m2 / (count - 1) as f64
+    var_ddof(data, 1)
+}
+
+/// Calculates the standard deviation of an array of data points with `ddof` delta degrees of
+/// freedom. This is the square root of [var_ddof](crate::statistics::var_ddof).
+pub fn std_ddof(data: &[f64], ddof: usize) -> f64 {
+    var_ddof(data, ddof).sqrt()
 }
 
 /// Calculates the standard deviation of an array of data points. This is the square root of the
@@ -70,6 +83,46 @@ pub fn sample_std(data: &[f64]) -> f64 {
     sample_var(data).sqrt()
 }
 
+/// Counts the number of NaN values in an array of data points.
+pub fn count_nan(data: &[f64]) -> usize {
+    data.iter().filter(|x| x.is_nan()).count()
+}
+
+/// Copies the values in an array of data points which are not NaN.
+fn non_nan(data: &[f64]) -> Vec<f64> {
+    data.iter().copied().filter(|x| !x.is_nan()).collect()
+}
+
+/// Calculates the sum of an array of data points, ignoring NaN values. Returns NaN if all of the
+/// values are NaN.
+pub fn nansum(data: &[f64]) -> f64 {
+    let values = non_nan(data);
+    if values.is_empty() {
+        return f64::NAN;
+    }
+    sum(&values)
+}
+
+/// Calculates the mean of an array of data points, ignoring NaN values. Returns NaN if all of the
+/// values are NaN.
+pub fn nanmean(data: &[f64]) -> f64 {
+    let values = non_nan(data);
+    if values.is_empty() {
+        return f64::NAN;
+    }
+    mean(&values)
+}
+
+/// Calculates the (population) standard deviation of an array of data points, ignoring NaN values.
+/// Returns NaN if all of the values are NaN.
+pub fn nanstd(data: &[f64]) -> f64 {
+    let values = non_nan(data);
+    if values.is_empty() {
+        return f64::NAN;
+    }
+    std(&values)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,6 +249,43 @@ mod tests {
         assert_approx_eq!(sample_var(&data2), 0.939837803612305);
     }
     #[test]
+    fn test_var_ddof() {
+        let data: Vec<f64> = vec![
+            -0.2711336,
+            1.20002575,
+            0.69102151,
+            -0.56390913,
+            -1.62661382,
+            -0.0613969,
+            0.39876752,
+            -0.99619281,
+            1.12860854,
+            -0.61163405,
+        ];
+        let n = data.len() as f64;
+        assert_approx_eq!(var_ddof(&data, 0), var(&data));
+        assert_approx_eq!(var_ddof(&data, 1), sample_var(&data));
+        assert_approx_eq!(var_ddof(&data, 1), var_ddof(&data, 0) * n / (n - 1.));
+        assert_approx_eq!(std_ddof(&data, 1), sample_std(&data));
+        assert_approx_eq!(var_ddof(&data, 2), var_ddof(&data, 0) * n / (n - 2.));
+    }
+    #[test]
+    fn test_nan_functions() {
+        let clean = vec![1.5, -0.3, 2.7, 0.9, -1.1];
+        let data = vec![1.5, f64::NAN, -0.3, 2.7, f64::NAN, 0.9, -1.1, f64::NAN];
+        assert_eq!(count_nan(&data), 3);
+        assert!(mean(&data).is_nan());
+        assert_approx_eq!(nansum(&data), sum(&clean));
+        assert_approx_eq!(nanmean(&data), mean(&clean));
+        assert_approx_eq!(nanstd(&data), std(&clean));
+
+        let all_nan = vec![f64::NAN; 4];
+        assert_eq!(count_nan(&all_nan), 4);
+        assert!(nansum(&all_nan).is_nan());
+        assert!(nanmean(&all_nan).is_nan());
+        assert!(nanstd(&all_nan).is_nan());
+    }
+    #[test]
     fn test_std() {
         let data1: Vec<f64> = vec![
             -0.2711336,
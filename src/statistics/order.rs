@@ -1,5 +1,7 @@
 //! A module for computing order statistics. This includes medians, quantiles, and extrema.
 
+use std::cmp::Ordering;
+
 /// Returns the smallest element in the array.
 pub fn min(data: &[f64]) -> f64 {
     data.iter().fold(f64::NAN, |acc, i| f64::min(acc, *i))
@@ -10,6 +12,28 @@ pub fn max(data: &[f64]) -> f64 {
     data.iter().fold(f64::NAN, |acc, i| f64::max(acc, *i))
 }
 
+/// Returns the running minimum of the array, where the `i`th element is the smallest of the first
+/// `i + 1` elements.
+pub fn cummin(data: &[f64]) -> Vec<f64> {
+    data.iter()
+        .scan(f64::NAN, |acc, &x| {
+            *acc = f64::min(*acc, x);
+            Some(*acc)
+        })
+        .collect()
+}
+
+/// Returns the running maximum of the array, where the `i`th element is the largest of the first
+/// `i + 1` elements.
+pub fn cummax(data: &[f64]) -> Vec<f64> {
+    data.iter()
+        .scan(f64::NAN, |acc, &x| {
+            *acc = f64::max(*acc, x);
+            Some(*acc)
+        })
+        .collect()
+}
+
 /// Returns the index of the smallest element in the array.
 pub fn argmin(data: &[f64]) -> usize {
     data.iter()
@@ -32,6 +56,195 @@ pub fn argmax(data: &[f64]) -> usize {
         .0
 }
 
+/// Returns the indices that would sort the array in ascending order, with any NaNs placed at the
+/// end. The sort is stable, so equal elements keep their original relative order.
+pub fn argsort(data: &[f64]) -> Vec<usize> {
+    let mut idx = (0..data.len()).collect::<Vec<_>>();
+    idx.sort_by(|&i, &j| nan_last_cmp(&data[i], &data[j], false));
+    idx
+}
+
+/// Returns the indices that would sort the array in descending order, with any NaNs placed at the
+/// end. The sort is stable, so equal elements keep their original relative order.
+pub fn argsort_desc(data: &[f64]) -> Vec<usize> {
+    let mut idx = (0..data.len()).collect::<Vec<_>>();
+    idx.sort_by(|&i, &j| nan_last_cmp(&data[i], &data[j], true));
+    idx
+}
+
+/// Compares two floats, ordering NaNs after every other value regardless of `descending`.
+pub(crate) fn nan_last_cmp(a: &f64, b: &f64, descending: bool) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) if descending => b.partial_cmp(a).unwrap(),
+        (false, false) => a.partial_cmp(b).unwrap(),
+    }
+}
+
+/// Ranks the data, starting from 1 for the smallest element. Tied elements are all given the
+/// average of the ranks that they span.
+pub fn rankdata(data: &[f64]) -> Vec<f64> {
+    let idx = argsort(data);
+    let mut ranks = vec![0.; data.len()];
+    let mut start = 0;
+    while start < idx.len() {
+        let mut end = start + 1;
+        while end < idx.len() && data[idx[end]] == data[idx[start]] {
+            end += 1;
+        }
+        // ranks start..end (zero-based) are tied, and average to this
+        let rank = (start + end + 1) as f64 / 2.;
+        for &i in &idx[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// [Quantile normalizes](https://en.wikipedia.org/wiki/Quantile_normalization) the columns of the
+/// `nrows x ncols` row-major matrix `data`, so that they all have the same distribution. The
+/// reference distribution is the mean of the sorted columns, and the `k`th smallest value in each
+/// column is replaced by the `k`th value of the reference. Tied values within a column are
+/// assigned consecutive reference values in their original order.
+///
+/// # Errors
+/// Panics if the length of `data` is not `nrows * ncols`.
+pub fn quantile_normalize(data: &[f64], nrows: usize, ncols: usize) -> Vec<f64> {
+    assert_eq!(data.len(), nrows * ncols, "matrix shape mismatch");
+
+    let orders = (0..ncols)
+        .map(|j| argsort(&(0..nrows).map(|i| data[i * ncols + j]).collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    let reference = (0..nrows)
+        .map(|k| {
+            orders
+                .iter()
+                .enumerate()
+                .map(|(j, order)| data[order[k] * ncols + j])
+                .sum::<f64>()
+                / ncols as f64
+        })
+        .collect::<Vec<_>>();
+
+    let mut normalized = vec![0.; data.len()];
+    for (j, order) in orders.iter().enumerate() {
+        for (k, &i) in order.iter().enumerate() {
+            normalized[i * ncols + j] = reference[k];
+        }
+    }
+    normalized
+}
+
+/// Calculates the `q`th quantile of the data, for `q` in `[0, 1]`. When the quantile falls between
+/// two data points, the result is linearly interpolated between them (the default method in numpy).
+///
+/// # Errors
+/// Panics if the data is empty or if `q` is not in `[0, 1]`.
+pub fn quantile(data: &[f64], q: f64) -> f64 {
+    assert!(!data.is_empty(), "data must not be empty");
+    assert!((0. ..=1.).contains(&q), "q must be between 0 and 1");
+
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let pos = q * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    sorted[lo] + (pos - lo as f64) * (sorted[hi] - sorted[lo])
+}
+
+/// Calculates the `p`th percentile of the data, for `p` in `[0, 100]`. See
+/// [quantile](crate::statistics::quantile).
+///
+/// # Errors
+/// Panics if the data is empty or if `p` is not in `[0, 100]`.
+pub fn percentile(data: &[f64], p: f64) -> f64 {
+    assert!((0. ..=100.).contains(&p), "p must be between 0 and 100");
+    quantile(data, p / 100.)
+}
+
+/// Calculates the percentile rank of `value` in the data. That is, the percentage of the data
+/// which is less than or equal to `value`. Values below the minimum have a rank of 0 and values at
+/// or above the maximum have a rank of 100.
+///
+/// # Errors
+/// Panics if the data is empty.
+pub fn percentile_rank(data: &[f64], value: f64) -> f64 {
+    assert!(!data.is_empty(), "data must not be empty");
+    100. * data.iter().filter(|&&x| x <= value).count() as f64 / data.len() as f64
+}
+
+/// Calculates the [empirical cumulative distribution
+/// function](https://en.wikipedia.org/wiki/Empirical_distribution_function) of the data. Returns
+/// the sorted unique values in the data and, for each of them, the fraction of the data which is
+/// less than or equal to that value.
+pub fn ecdf(data: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let n = sorted.len() as f64;
+    let mut values: Vec<f64> = Vec::new();
+    let mut probs = Vec::new();
+    for (i, &x) in sorted.iter().enumerate() {
+        // for repeated values, only keep the cumulative fraction at the last occurrence
+        if values.last() == Some(&x) {
+            *probs.last_mut().unwrap() = (i + 1) as f64 / n;
+        } else {
+            values.push(x);
+            probs.push((i + 1) as f64 / n);
+        }
+    }
+    (values, probs)
+}
+
+/// Evaluates the empirical cumulative distribution function of the data at `x`. That is, the
+/// fraction of the data which is less than or equal to `x`.
+///
+/// # Errors
+/// Panics if the data is empty.
+pub fn ecdf_at(data: &[f64], x: f64) -> f64 {
+    percentile_rank(data, x) / 100.
+}
+
+/// Calculates the median of the data.
+///
+/// # Errors
+/// Panics if the data is empty.
+pub fn median(data: &[f64]) -> f64 {
+    quantile(data, 0.5)
+}
+
+/// Calculates the interquartile range of the data. That is, the difference between the 75th and
+/// 25th percentiles.
+///
+/// # Errors
+/// Panics if the data is empty.
+pub fn iqr(data: &[f64]) -> f64 {
+    quantile(data, 0.75) - quantile(data, 0.25)
+}
+
+/// Finds outliers in the data using [Tukey's fences](https://en.wikipedia.org/wiki/Outlier#Tukey's_fences).
+/// Returns the indices of the points which lie outside `[Q1 - k * IQR, Q3 + k * IQR]`, where `Q1`
+/// and `Q3` are the first and third quartiles and `IQR` is the interquartile range. Tukey
+/// suggested `k = 1.5` for outliers and `k = 3` for "far out" points.
+///
+/// # Errors
+/// Panics if the data is empty.
+pub fn tukey_outliers(data: &[f64], k: f64) -> Vec<usize> {
+    let q1 = quantile(data, 0.25);
+    let q3 = quantile(data, 0.75);
+    let lower = q1 - k * (q3 - q1);
+    let upper = q3 + k * (q3 - q1);
+    data.iter()
+        .enumerate()
+        .filter(|(_, &x)| x < lower || x > upper)
+        .map(|(i, _)| i)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,6 +281,20 @@ mod tests {
         assert_approx_eq!(min(&data2), -1.35521905);
     }
 
+    #[test]
+    fn test_cummax_cummin() {
+        let data = [3., 1., 4., 1., 5., 9., 2., 6., -5.];
+        let upper = cummax(&data);
+        let lower = cummin(&data);
+        assert_eq!(upper, vec![3., 3., 4., 4., 5., 9., 9., 9., 9.]);
+        assert_eq!(lower, vec![3., 1., 1., 1., 1., 1., 1., 1., -5.]);
+        assert!(upper.windows(2).all(|w| w[0] <= w[1]));
+        assert!(lower.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(upper[data.len() - 1], max(&data));
+        assert_eq!(lower[data.len() - 1], min(&data));
+        assert!(cummax(&[]).is_empty());
+    }
+
     #[test]
     fn test_argmin() {
         let data1: Vec<f64> = vec![
@@ -160,4 +387,120 @@ mod tests {
         ];
         assert_eq!(argmax(&data2), 7);
     }
+
+    #[test]
+    fn test_argsort() {
+        let data = [0.3, -1.2, 2.5, 0.3, -4., 1.1, 0.3];
+        let idx = argsort(&data);
+        let sorted = idx.iter().map(|&i| data[i]).collect::<Vec<_>>();
+        assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(idx, vec![4, 1, 0, 3, 6, 5, 2]);
+
+        let idx = argsort_desc(&data);
+        let sorted = idx.iter().map(|&i| data[i]).collect::<Vec<_>>();
+        assert!(sorted.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(idx, vec![2, 5, 0, 3, 6, 1, 4]);
+
+        let data = [1., f64::NAN, -2., 3., f64::NAN];
+        assert_eq!(argsort(&data), vec![2, 0, 3, 1, 4]);
+        assert_eq!(argsort_desc(&data), vec![3, 0, 2, 1, 4]);
+    }
+
+    #[test]
+    fn test_rankdata() {
+        assert_eq!(rankdata(&[3., 1., 2.]), vec![3., 1., 2.]);
+        assert_eq!(
+            rankdata(&[2., 5., 2., 1., 5., 5.]),
+            vec![2.5, 5., 2.5, 1., 5., 5.]
+        );
+        assert!(rankdata(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_quantile_normalize() {
+        let data = [5., 4., 3., 2., 1., 4., 3., 4., 6., 4., 2., 8.];
+        let normalized = quantile_normalize(&data, 4, 3);
+        let columns = (0..3)
+            .map(|j| {
+                let mut col = (0..4).map(|i| normalized[i * 3 + j]).collect::<Vec<_>>();
+                col.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                col
+            })
+            .collect::<Vec<_>>();
+        // the mean of the sorted columns
+        let reference = vec![2., 3., 14. / 3., 17. / 3.];
+        for col in &columns {
+            for (a, b) in col.iter().zip(&reference) {
+                assert_approx_eq!(*a, *b);
+            }
+        }
+        // the order within each column is preserved
+        assert_eq!(normalized[0], 17. / 3.);
+        assert_eq!(normalized[3 + 2], 3.);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let data = [3., -1., 7., 2., 5.];
+        assert_eq!(quantile(&data, 0.), -1.);
+        assert_eq!(quantile(&data, 1.), 7.);
+        assert_eq!(median(&data), 3.);
+        assert_approx_eq!(quantile(&data, 0.3), 2.2);
+        assert_approx_eq!(median(&[4., 1., 2., 3.]), 2.5);
+        assert_approx_eq!(iqr(&data), 3.);
+    }
+
+    #[test]
+    #[should_panic(expected = "q must be between 0 and 1")]
+    fn test_quantile_out_of_range() {
+        quantile(&[1., 2.], 1.5);
+    }
+
+    #[test]
+    fn test_percentile_rank() {
+        let data = (0..101)
+            .map(|i| ((i * 37) % 101) as f64 * 0.5)
+            .collect::<Vec<_>>();
+        assert_approx_eq!(percentile_rank(&data, median(&data)), 50., 0.02);
+        assert_eq!(percentile_rank(&data, -1.), 0.);
+        assert_eq!(percentile_rank(&data, 50.), 100.);
+        assert_eq!(percentile_rank(&data, 1000.), 100.);
+
+        // round trip to within the spacing of the data
+        for &x in &[3., 12.5, 25., 41.] {
+            assert!((percentile(&data, percentile_rank(&data, x)) - x).abs() <= 0.5);
+        }
+        for &p in &[10., 30., 50., 90.] {
+            assert!((percentile_rank(&data, percentile(&data, p)) - p).abs() <= 1.);
+        }
+    }
+
+    #[test]
+    fn test_ecdf() {
+        let data = [2., -1., 3.5, 2., 0., 2., 7.];
+        let (values, probs) = ecdf(&data);
+        assert_eq!(values, vec![-1., 0., 2., 3.5, 7.]);
+        let expected = [1., 2., 5., 6., 7.];
+        for i in 0..probs.len() {
+            assert_approx_eq!(probs[i], expected[i] / 7.);
+        }
+        assert!(probs.windows(2).all(|w| w[0] < w[1]));
+
+        assert_eq!(ecdf_at(&data, 7.), 1.);
+        assert_eq!(ecdf_at(&data, 100.), 1.);
+        assert_eq!(ecdf_at(&data, -1.5), 0.);
+        assert_approx_eq!(ecdf_at(&data, 2.5), 5. / 7.);
+    }
+
+    #[test]
+    fn test_tukey_outliers() {
+        let mut data = vec![
+            9.8, 10.1, 10.4, 9.7, 10.0, 10.2, 9.9, 10.3, 9.6, 10.05, 9.95, 10.15,
+        ];
+        assert!(tukey_outliers(&data, 1.5).is_empty());
+
+        data.insert(4, 25.);
+        data.push(-3.);
+        assert_eq!(tukey_outliers(&data, 1.5), vec![4, 13]);
+    }
 }
@@ -0,0 +1,135 @@
+//! Streaming (one-pass) estimation of quantiles.
+
+use crate::statistics::quantile;
+
+/// Estimates a single quantile of a stream of data in constant memory using the [P-square
+/// algorithm](https://www.cse.wustl.edu/~jain/papers/ftp/psqr.pdf) of Jain and Chlamtac (1985).
+/// Five markers track the minimum, the maximum, the desired quantile, and the quantiles halfway
+/// between it and the extremes; their heights are adjusted with piecewise-parabolic interpolation
+/// as each observation arrives.
+#[derive(Debug, Clone)]
+pub struct P2Quantile {
+    p: f64,
+    count: usize,
+    heights: [f64; 5],
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl P2Quantile {
+    /// Create a new estimator for the `p`th quantile, where `p` is in `[0, 1]`.
+    pub fn new(p: f64) -> Self {
+        assert!((0. ..=1.).contains(&p), "p must be between 0 and 1");
+        Self {
+            p,
+            count: 0,
+            heights: [0.; 5],
+            positions: [1., 2., 3., 4., 5.],
+            desired: [1., 1. + 2. * p, 1. + 4. * p, 3. + 2. * p, 5.],
+            increments: [0., p / 2., p, (1. + p) / 2., 1.],
+        }
+    }
+
+    /// Returns the number of observations seen so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Add an observation to the estimator.
+    pub fn push(&mut self, x: f64) {
+        if self.count < 5 {
+            self.heights[self.count] = x;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            }
+            return;
+        }
+        self.count += 1;
+
+        // find the cell containing x, extending the extremes if necessary
+        let q = &mut self.heights;
+        let k = if x < q[0] {
+            q[0] = x;
+            0
+        } else if x >= q[4] {
+            q[4] = x;
+            3
+        } else {
+            (0..4).find(|&i| x < q[i + 1]).unwrap()
+        };
+
+        for i in (k + 1)..5 {
+            self.positions[i] += 1.;
+        }
+        for i in 0..5 {
+            self.desired[i] += self.increments[i];
+        }
+
+        // adjust the heights of the middle markers if they are off their desired positions
+        for i in 1..4 {
+            let n = &mut self.positions;
+            let d = self.desired[i] - n[i];
+            if (d >= 1. && n[i + 1] - n[i] > 1.) || (d <= -1. && n[i - 1] - n[i] < -1.) {
+                let d = d.signum();
+                let parabolic = q[i]
+                    + d / (n[i + 1] - n[i - 1])
+                        * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                            + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]));
+                q[i] = if q[i - 1] < parabolic && parabolic < q[i + 1] {
+                    parabolic
+                } else {
+                    let j = if d > 0. { i + 1 } else { i - 1 };
+                    q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+                };
+                n[i] += d;
+            }
+        }
+    }
+
+    /// Returns the current estimate of the quantile. With fewer than five observations, the exact
+    /// quantile of the observations is returned. Returns NaN if there are no observations.
+    pub fn quantile(&self) -> f64 {
+        match self.count {
+            0 => f64::NAN,
+            1..=4 => quantile(&self.heights[..self.count], self.p),
+            _ => self.heights[2],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{Distribution1D, Exponential, Normal};
+
+    #[test]
+    fn test_p2_quantile() {
+        alea::set_seed(7);
+        for data in &[
+            Normal::new(2., 3.).sample_n(50000),
+            Exponential::new(0.5).sample_n(50000),
+        ] {
+            for &p in &[0.5, 0.9] {
+                let mut est = P2Quantile::new(p);
+                for &x in data.iter() {
+                    est.push(x);
+                }
+                assert_eq!(est.count(), data.len());
+                let exact = quantile(data, p);
+                assert!((est.quantile() - exact).abs() < 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn test_p2_quantile_few() {
+        let mut est = P2Quantile::new(0.5);
+        assert!(est.quantile().is_nan());
+        for &x in &[3., 1., 2.] {
+            est.push(x);
+        }
+        assert_eq!(est.quantile(), 2.);
+    }
+}
@@ -0,0 +1,92 @@
+//! Summary statistics of data.
+
+use super::{max, mean, median, min, quantile, sample_std};
+use std::fmt::{Display, Formatter, Result};
+
+/// Summary statistics of some data, as returned by [describe].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    /// Sample standard deviation.
+    pub std: f64,
+    pub min: f64,
+    /// First quartile.
+    pub q25: f64,
+    pub median: f64,
+    /// Third quartile.
+    pub q75: f64,
+    pub max: f64,
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        writeln!(f, "{:<8}{:>14}", "count", self.count)?;
+        let rows = [
+            ("mean", self.mean),
+            ("std", self.std),
+            ("min", self.min),
+            ("25%", self.q25),
+            ("50%", self.median),
+            ("75%", self.q75),
+            ("max", self.max),
+        ];
+        for (name, value) in rows.iter() {
+            writeln!(f, "{:<8}{:>14.6}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Calculates summary statistics of the data: the number of elements, the mean, the sample
+/// standard deviation, the minimum, the quartiles, and the maximum. The quartiles are computed with
+/// [quantile](crate::statistics::quantile).
+///
+/// # Errors
+/// Panics if the data is empty.
+pub fn describe(data: &[f64]) -> Summary {
+    assert!(!data.is_empty(), "data must not be empty");
+    Summary {
+        count: data.len(),
+        mean: mean(data),
+        std: if data.len() > 1 {
+            sample_std(data)
+        } else {
+            f64::NAN
+        },
+        min: min(data),
+        q25: quantile(data, 0.25),
+        median: median(data),
+        q75: quantile(data, 0.75),
+        max: max(data),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        let data = [2., 8., -1., 4.5, 3., 10., 0.5, 6.];
+        let summary = describe(&data);
+        assert_eq!(summary.count, 8);
+        assert_eq!(summary.mean, mean(&data));
+        assert_eq!(summary.std, sample_std(&data));
+        assert_eq!(summary.min, -1.);
+        assert_eq!(summary.q25, quantile(&data, 0.25));
+        assert_eq!(summary.median, median(&data));
+        assert_eq!(summary.q75, quantile(&data, 0.75));
+        assert_eq!(summary.max, 10.);
+
+        let table = summary.to_string();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 8);
+        assert!(lines.iter().all(|l| l.len() == lines[0].len()));
+        assert_eq!(lines[0], format!("count   {:>14}", 8));
+        assert_eq!(lines[3], format!("min     {:>14}", "-1.000000"));
+
+        assert!(describe(&[1.]).std.is_nan());
+    }
+}
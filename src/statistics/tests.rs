@@ -1,20 +1,426 @@
+//! Statistical hypothesis tests.
+
+use super::{mean, rankdata, sample_std, sample_var};
+use crate::distributions::{ChiSquared, FisherSnedecor, T};
+use crate::functions::erfc;
+use std::f64::consts::SQRT_2;
+
+/// Calculates the survival function (one minus the cumulative distribution function) of the
+/// standard normal distribution at `z`, i.e., the probability that a standard normal random
+/// variable is greater than `z`.
+pub fn normal_sf(z: f64) -> f64 {
+    // erfc avoids the cancellation in 1 - cdf(z) for large z
+    0.5 * erfc(z / SQRT_2)
+}
+
+/// Calculates the two-sided p-value for a test statistic `z` that has a standard normal
+/// distribution under the null hypothesis, i.e., the probability that a standard normal random
+/// variable is at least as large as `z` in absolute value.
+pub fn normal_pvalue_two_sided(z: f64) -> f64 {
+    (2. * normal_sf(z.abs())).min(1.)
+}
+
+/// Calculates the [Mann-Whitney U test](https://en.wikipedia.org/wiki/Mann%E2%80%93Whitney_U_test)
+/// for two independent samples. It tests the null hypothesis that a random draw from the
+/// population of `a` is equally likely to be larger or smaller than one from the population of
+/// `b`, without assuming normality. Returns the U statistic of `a` and the two-sided p-value from a
+/// normal approximation with tie and continuity corrections, which is reasonable when both samples
+/// have more than about 20 elements.
+///
+/// # Errors
+/// Panics if either sample is empty.
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> (f64, f64) {
+    assert!(!a.is_empty() && !b.is_empty(), "samples must not be empty");
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    let n = n1 + n2;
+
+    let combined = [a, b].concat();
+    let ranks = rankdata(&combined);
+    let u = ranks[..a.len()].iter().sum::<f64>() - n1 * (n1 + 1.) / 2.;
+
+    let mu = n1 * n2 / 2.;
+    let sigma = (n1 * n2 / 12. * ((n + 1.) - tie_sum(&combined) / (n * (n - 1.)))).sqrt();
+    let pvalue = if sigma > 0. {
+        normal_pvalue_two_sided(((u - mu).abs() - 0.5).max(0.) / sigma)
+    } else {
+        1.
+    };
+    (u, pvalue)
+}
+
+/// Calculates the [Wilcoxon signed-rank
+/// test](https://en.wikipedia.org/wiki/Wilcoxon_signed-rank_test) for paired samples. It tests the
+/// null hypothesis that the differences `a - b` are symmetric about zero, without assuming
+/// normality. Pairs with no difference are dropped, and the remaining absolute differences are
+/// ranked. Returns the sum of the signed ranks and the two-sided p-value from a normal
+/// approximation with tie and continuity corrections, which is reasonable when there are more than
+/// about 20 nonzero differences.
+///
+/// # Errors
+/// Panics if `a` and `b` have different lengths.
+pub fn wilcoxon(a: &[f64], b: &[f64]) -> (f64, f64) {
+    assert_eq!(a.len(), b.len(), "samples must have the same length");
+    let diffs = a
+        .iter()
+        .zip(b)
+        .map(|(x, y)| x - y)
+        .filter(|&d| d != 0.)
+        .collect::<Vec<_>>();
+    let n = diffs.len() as f64;
+
+    let abs_diffs = diffs.iter().map(|d| d.abs()).collect::<Vec<_>>();
+    let ranks = rankdata(&abs_diffs);
+    let w = diffs
+        .iter()
+        .zip(&ranks)
+        .map(|(d, r)| d.signum() * r)
+        .sum::<f64>();
+
+    // the signed rank sum moves in steps of 2, so the continuity correction is 1
+    let sigma = (n * (n + 1.) * (2. * n + 1.) / 6. - tie_sum(&abs_diffs) / 12.).sqrt();
+    let pvalue = if sigma > 0. {
+        normal_pvalue_two_sided((w.abs() - 1.).max(0.) / sigma)
+    } else {
+        1.
+    };
+    (w, pvalue)
+}
+
+/// Calculates the [Anderson-Darling
+/// test](https://en.wikipedia.org/wiki/Anderson%E2%80%93Darling_test) for normality, with the mean
+/// and variance estimated from the data. It tests the null hypothesis that the data comes from a
+/// normal distribution. Returns the `A^2` statistic with the small-sample correction
+/// `A^2 (1 + 0.75 / n + 2.25 / n^2)`, and the p-value from the approximation of D'Agostino and
+/// Stephens (1986).
+///
+/// # Errors
+/// Panics if there are fewer than three data points, or if all of the data is the same.
+pub fn anderson_darling_normal(data: &[f64]) -> (f64, f64) {
+    assert!(data.len() > 2, "need at least three data points");
+    let n = data.len() as f64;
+    let (mu, sigma) = (mean(data), sample_std(data));
+    assert!(sigma > 0., "data must not be constant");
+
+    let mut z = data.iter().map(|x| (x - mu) / sigma).collect::<Vec<_>>();
+    z.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    // ln(F(z)) and ln(1 - F(z)), guarding against underflow in the tails
+    let ln_cdf = |z: f64| normal_sf(-z).max(f64::MIN_POSITIVE).ln();
+    let ln_sf = |z: f64| normal_sf(z).max(f64::MIN_POSITIVE).ln();
+
+    let s = z
+        .iter()
+        .zip(z.iter().rev())
+        .enumerate()
+        .map(|(i, (&lo, &hi))| (2 * i + 1) as f64 * (ln_cdf(lo) + ln_sf(hi)))
+        .sum::<f64>();
+    let a2 = (-n - s / n) * (1. + 0.75 / n + 2.25 / n.powi(2));
+
+    let pvalue = if a2 >= 0.6 {
+        // the approximation starts increasing again past its minimum at about 153
+        (1.2937 - 5.709 * a2.min(153.) + 0.0186 * a2.min(153.).powi(2)).exp()
+    } else if a2 >= 0.34 {
+        (0.9177 - 4.279 * a2 - 1.38 * a2.powi(2)).exp()
+    } else if a2 >= 0.2 {
+        1. - (-8.318 + 42.796 * a2 - 59.938 * a2.powi(2)).exp()
+    } else {
+        1. - (-13.436 + 101.14 * a2 - 223.73 * a2.powi(2)).exp()
+    };
+    (a2, pvalue.clamp(0., 1.))
+}
+
+/// Calculates [Pearson's chi-squared test of
+/// independence](https://en.wikipedia.org/wiki/Pearson%27s_chi-squared_test#Testing_for_statistical_independence)
+/// for the `nrows x ncols` row-major contingency table `table`. It tests the null hypothesis that
+/// the row and column variables are independent, by comparing the observed counts with those
+/// expected from the row and column totals. Returns the chi-squared statistic, the p-value, and the
+/// degrees of freedom `(nrows - 1) * (ncols - 1)`. No continuity correction is applied.
+///
+/// # Errors
+/// Panics if the shape of the table does not match `nrows` and `ncols`, if there are fewer than two
+/// rows or columns, or if any row or column total is zero.
+pub fn chi2_independence(table: &[usize], nrows: usize, ncols: usize) -> (f64, f64, usize) {
+    assert_eq!(table.len(), nrows * ncols, "matrix shape mismatch");
+    assert!(
+        nrows > 1 && ncols > 1,
+        "table must have at least two rows and two columns"
+    );
+
+    let row_totals = table
+        .chunks_exact(ncols)
+        .map(|row| row.iter().sum::<usize>() as f64)
+        .collect::<Vec<_>>();
+    let col_totals = (0..ncols)
+        .map(|j| (0..nrows).map(|i| table[i * ncols + j]).sum::<usize>() as f64)
+        .collect::<Vec<_>>();
+    assert!(
+        row_totals.iter().chain(&col_totals).all(|&t| t > 0.),
+        "row and column totals must be positive"
+    );
+    let total = row_totals.iter().sum::<f64>();
+
+    let mut stat = 0.;
+    for (i, row_total) in row_totals.iter().enumerate() {
+        for (j, col_total) in col_totals.iter().enumerate() {
+            let expected = row_total * col_total / total;
+            stat += (table[i * ncols + j] as f64 - expected).powi(2) / expected;
+        }
+    }
+    let dof = (nrows - 1) * (ncols - 1);
+    let pvalue = 1. - ChiSquared::new(dof).cdf(stat);
+    (stat, pvalue, dof)
+}
+
+/// Calculates the [one-way analysis of
+/// variance](https://en.wikipedia.org/wiki/One-way_analysis_of_variance) for two or more groups,
+/// which may have different sizes. It tests the null hypothesis that all of the groups have the
+/// same population mean, assuming that they are normally distributed with equal variances. Returns
+/// the F statistic, which is the ratio of the between-group and within-group mean squares, and the
+/// p-value from the F distribution with `k - 1` and `n - k` degrees of freedom, where `k` is the
+/// number of groups and `n` is the total number of observations.
+///
+/// # Errors
+/// Panics if there are fewer than two groups, if any group is empty, or if there are no more
+/// observations than groups.
+pub fn anova_oneway(groups: &[&[f64]]) -> (f64, f64) {
+    assert!(groups.len() > 1, "need at least two groups");
+    assert!(
+        groups.iter().all(|g| !g.is_empty()),
+        "groups must not be empty"
+    );
+    let k = groups.len();
+    let n = groups.iter().map(|g| g.len()).sum::<usize>();
+    assert!(n > k, "need more observations than groups");
+
+    let grand_mean = groups.iter().flat_map(|g| g.iter()).sum::<f64>() / n as f64;
+    let mut ss_between = 0.;
+    let mut ss_within = 0.;
+    for group in groups {
+        let group_mean = mean(group);
+        ss_between += group.len() as f64 * (group_mean - grand_mean).powi(2);
+        ss_within += group.iter().map(|x| (x - group_mean).powi(2)).sum::<f64>();
+    }
+
+    let (dof_between, dof_within) = ((k - 1) as f64, (n - k) as f64);
+    let f = (ss_between / dof_between) / (ss_within / dof_within);
+    let pvalue = 1. - FisherSnedecor::new(dof_between, dof_within).cdf(f);
+    (f, pvalue)
+}
+
+/// Calculates `sum(t^3 - t)` over the groups of tied values in the data, where `t` is the size of
+/// each group, as used in tie corrections for rank tests.
+fn tie_sum(data: &[f64]) -> f64 {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut total = 0.;
+    let mut start = 0;
+    while start < sorted.len() {
+        let mut end = start + 1;
+        while end < sorted.len() && sorted[end] == sorted[start] {
+            end += 1;
+        }
+        let t = (end - start) as f64;
+        total += t.powi(3) - t;
+        start = end;
+    }
+    total
+}
+
 /// Calculates the t-test for the mean of one set of data. It tests for the null hypothesis that
 /// the mean of a sample of independent observations `data` is equal to the population mean `mu`.
 /// It returns the t statistic and the two-sided p-value.
+///
+/// # Errors
+/// Panics if there are fewer than two data points.
 pub fn ttest_1s(data: &[f64], mu: f64) -> (f64, f64) {
-    unimplemented!();
+    assert!(data.len() > 1, "need at least two data points");
+    let n = data.len() as f64;
+    let t = (mean(data) - mu) / (sample_std(data) / n.sqrt());
+    (t, t_pvalue_two_sided(t, n - 1.))
 }
 
 /// Calculates the Student's t-test for two independent samples, assuming equal variance. This
 /// is less reliable than Welch's t-test. See <https://en.wikipedia.org/wiki/Student%27s_t-test>.
+/// It returns the t statistic and the two-sided p-value.
+///
+/// # Errors
+/// Panics if either sample has fewer than two data points.
 pub fn ttest_2s_student(x: &[f64], y: &[f64]) -> (f64, f64) {
-    unimplemented!();
+    assert!(
+        x.len() > 1 && y.len() > 1,
+        "need at least two data points in each sample"
+    );
+    let (nx, ny) = (x.len() as f64, y.len() as f64);
+    let dof = nx + ny - 2.;
+    let pooled_var = ((nx - 1.) * sample_var(x) + (ny - 1.) * sample_var(y)) / dof;
+    let t = (mean(x) - mean(y)) / (pooled_var * (1. / nx + 1. / ny)).sqrt();
+    (t, t_pvalue_two_sided(t, dof))
 }
 
 /// Calculates Welch's t-test for two independent samples, without assuming equal variance. It tests
 /// the hypothesis that the two populations have equal means. This is more reliable when the two
 /// samples have unequal variances and/or unequal sample sizes. See
-/// <https://en.wikipedia.org/wiki/Welch%27s_t-test>.
+/// <https://en.wikipedia.org/wiki/Welch%27s_t-test>. It returns the t statistic and the two-sided
+/// p-value, using the Welch-Satterthwaite approximation for the degrees of freedom.
+///
+/// # Errors
+/// Panics if either sample has fewer than two data points.
 pub fn ttest_2s_welch(x: &[f64], y: &[f64]) -> (f64, f64) {
-    unimplemented!();
+    assert!(
+        x.len() > 1 && y.len() > 1,
+        "need at least two data points in each sample"
+    );
+    let (nx, ny) = (x.len() as f64, y.len() as f64);
+    let (vx, vy) = (sample_var(x) / nx, sample_var(y) / ny);
+    let t = (mean(x) - mean(y)) / (vx + vy).sqrt();
+    let dof = (vx + vy).powi(2) / (vx.powi(2) / (nx - 1.) + vy.powi(2) / (ny - 1.));
+    (t, t_pvalue_two_sided(t, dof))
+}
+
+/// Calculates the two-sided p-value for a test statistic `t` that has a t distribution with `dof`
+/// degrees of freedom under the null hypothesis.
+fn t_pvalue_two_sided(t: f64, dof: f64) -> f64 {
+    (2. * T::new(dof).cdf(-t.abs())).min(1.)
+}
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod tests {
+    use super::*;
+    use crate::distributions::{Distribution1D, Exponential, Normal};
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_normal_pvalues() {
+        assert_approx_eq!(normal_sf(0.), 0.5, 1e-6);
+        assert_approx_eq!(normal_sf(1.), 0.15865525393145707, 1e-6);
+        assert_approx_eq!(normal_sf(-1.), 1. - 0.15865525393145707, 1e-6);
+        assert_approx_eq!(normal_pvalue_two_sided(1.96), 0.04999579029644087, 1e-5);
+        assert_approx_eq!(normal_pvalue_two_sided(-1.96), 0.04999579029644087, 1e-5);
+        assert_approx_eq!(normal_pvalue_two_sided(0.), 1., 1e-6);
+        // no cancellation far into the upper tail
+        assert_approx_eq!(normal_sf(10.), 7.619853024160593e-24, 1e-6);
+        assert_approx_eq!(normal_sf(30.), 4.906713927148764e-198, 1e-6);
+    }
+
+    #[test]
+    fn test_ttests() {
+        let x = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 5.7, 6.2];
+        let y = [4.8, 5.0, 4.6, 5.2, 4.4, 4.9, 5.1, 4.3, 4.7, 5.5, 4.2];
+
+        let (t, p) = ttest_1s(&x, 5.);
+        assert_approx_eq!(t, 3.643130718721436);
+        assert_approx_eq!(p, 0.008252749523002513, 1e-5);
+
+        let (t, p) = ttest_2s_student(&x, &y);
+        assert_approx_eq!(t, 4.014198823830603);
+        assert_approx_eq!(p, 0.000899118392620012, 1e-5);
+
+        let (t, p) = ttest_2s_welch(&x, &y);
+        assert_approx_eq!(t, 3.9432526375906236);
+        assert_approx_eq!(p, 0.001433141874322081, 1e-5);
+
+        // swapping the samples flips the sign of the statistic only
+        let (t, q) = ttest_2s_welch(&y, &x);
+        assert_approx_eq!(t, -3.9432526375906236);
+        assert_approx_eq!(q, p);
+        assert_approx_eq!(ttest_1s(&x, mean(&x)).1, 1.);
+    }
+
+    #[test]
+    fn test_mann_whitney_u() {
+        let a = [1., 2., 3., 4., 5.];
+        let b = [6., 7., 8., 9., 10.];
+        let (u, p) = mann_whitney_u(&a, &b);
+        assert_eq!(u, 0.);
+        assert_approx_eq!(p, 0.012185780355344818, 1e-4);
+        assert_eq!(mann_whitney_u(&b, &a).0, 25.);
+
+        // ties are given average ranks
+        let (u, _) = mann_whitney_u(&[1., 2., 2.], &[2., 3.]);
+        assert_eq!(u, 1.);
+
+        alea::set_seed(9);
+        let dist = Normal::new(0., 1.);
+        let x = dist.sample_n(50);
+        let y = dist.sample_n(60);
+        let shifted = y.iter().map(|v| v + 2.).collect::<Vec<_>>();
+        assert!(mann_whitney_u(&x, &shifted).1 < 1e-6);
+        assert!(mann_whitney_u(&x, &y).1 > 0.05);
+    }
+
+    #[test]
+    fn test_anderson_darling_normal() {
+        let data = [2.3, -0.4, 1.1, 0.8, 3.5, 1.9, 0.2, 1.4, 2.8, 0.9, 1.6, -1.2];
+        let (a2, p) = anderson_darling_normal(&data);
+        assert_approx_eq!(a2, 0.11296588698236945, 1e-4);
+        assert_approx_eq!(p, 0.9922936758198776, 1e-4);
+
+        alea::set_seed(12);
+        let normal = Normal::new(3., 2.).sample_n(200);
+        assert!(anderson_darling_normal(&normal).1 > 0.05);
+        let skewed = Exponential::new(1.).sample_n(200);
+        assert!(anderson_darling_normal(&skewed).1 < 1e-4);
+        let lognormal = normal.iter().map(|x| x.exp()).collect::<Vec<_>>();
+        assert!(anderson_darling_normal(&lognormal).1 < 1e-4);
+    }
+
+    #[test]
+    fn test_chi2_independence() {
+        let (stat, p, dof) = chi2_independence(&[10, 20, 30, 40], 2, 2);
+        assert_approx_eq!(stat, 0.7936507936507936);
+        assert_approx_eq!(p, 0.37299848361348714, 1e-8);
+        assert_eq!(dof, 1);
+
+        let (stat, p, dof) = chi2_independence(&[20, 5, 10, 15, 5, 25], 3, 2);
+        assert_approx_eq!(stat, 22.433862433862434);
+        assert_approx_eq!(p, 1.3444624235144016e-5, 1e-6);
+        assert_eq!(dof, 2);
+
+        // rows proportional to each other are exactly independent
+        let (stat, p, _) = chi2_independence(&[10, 20, 5, 30, 60, 15], 2, 3);
+        assert!(stat.abs() < 1e-12);
+        assert_approx_eq!(p, 1.);
+    }
+
+    #[test]
+    fn test_anova_oneway() {
+        let (f, p) = anova_oneway(&[&[1., 2., 3.], &[4., 5., 6.], &[7., 8., 9.]]);
+        assert_approx_eq!(f, 27.);
+        assert_approx_eq!(p, 0.001, 1e-8);
+
+        // unequal group sizes
+        let (f, _) = anova_oneway(&[&[2., 4.], &[1., 3., 5., 7.], &[6.]]);
+        assert_approx_eq!(f, (6. / 2.) / (22. / 4.));
+
+        alea::set_seed(13);
+        let a = Normal::new(0., 1.).sample_n(40);
+        let b = Normal::new(0., 1.).sample_n(25);
+        let c = Normal::new(1.5, 1.).sample_n(30);
+        assert!(anova_oneway(&[&a, &b, &c]).1 < 1e-6);
+        assert!(anova_oneway(&[&a, &b]).1 > 0.05);
+    }
+
+    #[test]
+    fn test_wilcoxon() {
+        alea::set_seed(10);
+        let a = Normal::new(5., 2.).sample_n(30);
+
+        let b = a.iter().map(|x| x + 0.5).collect::<Vec<_>>();
+        let (w, p) = wilcoxon(&a, &b);
+        assert_eq!(w, -465.);
+        assert!(p < 1e-4);
+
+        // differences which alternate in sign
+        let b = a
+            .iter()
+            .enumerate()
+            .map(|(i, x)| x + (-1_f64).powi(i as i32) * i as f64 / 100.)
+            .collect::<Vec<_>>();
+        assert!(wilcoxon(&a, &b).1 > 0.05);
+        assert_eq!(wilcoxon(&a, &a), (0., 1.));
+
+        // zero differences are dropped
+        let (w, _) = wilcoxon(&[1., 2., 3., 4.], &[1., 1., 5., 1.]);
+        assert_eq!(w, 1. - 2. + 3.);
+    }
 }
@@ -1,4 +1,6 @@
+use crate::functions::{fft, ifft};
 use crate::statistics::mean;
+use std::f64::consts::PI;
 
 /// Calculates the autocovariance of lag (-)k of a vector of time series data,
 /// assuming that the points are equally spaced in time.
@@ -30,17 +32,388 @@ pub fn acf(ts: &[f64], k: i32) -> f64 {
     numerator / denominator
 }
 
+/// Calculates the autocorrelations of lags `0` to `max_lag` (inclusive) of a vector of time series
+/// data, assuming that the points are equally spaced in time. This gives the same results as
+/// [acf], but computes all of the lags at once in `O(n log n)` time using the fast Fourier
+/// transform and the [Wiener-Khinchin
+/// theorem](https://en.wikipedia.org/wiki/Wiener%E2%80%93Khinchin_theorem).
+///
+/// # Errors
+/// Panics if `max_lag` is not less than the length of the time series.
+pub fn acf_fft(ts: &[f64], max_lag: usize) -> Vec<f64> {
+    let n = ts.len();
+    assert!(
+        max_lag < n,
+        "max_lag must be less than the length of the time series"
+    );
+    let ts_mean = mean(ts);
+
+    // zero-pad to at least 2n so that the circular correlation does not wrap around
+    let mut centered = ts.iter().map(|x| x - ts_mean).collect::<Vec<_>>();
+    centered.resize(2 * n, 0.);
+    let power = fft(&centered)
+        .into_iter()
+        .map(|(re, im)| (re * re + im * im, 0.))
+        .collect::<Vec<_>>();
+    let acovs = ifft(&power);
+
+    (0..=max_lag).map(|k| acovs[k].0 / acovs[0].0).collect()
+}
+
 /// Applies a single differencing operation to a vector. Note that the length of the vector is shortened by
 /// one.
 pub fn difference(v: Vec<f64>) -> Vec<f64> {
     (0..v.len() - 1).map(|i| v[i + 1] - v[i]).collect()
 }
 
+/// Calculates the median of each window of length `window` sliding over a vector of time series
+/// data. This is useful for removing isolated spikes. The output has length `ts.len() - window + 1`.
+pub fn rolling_median(ts: &[f64], window: usize) -> Vec<f64> {
+    assert!(
+        window > 0 && window <= ts.len(),
+        "window must be positive and no longer than the time series"
+    );
+
+    // keep a sorted copy of the current window, updating it as the window slides
+    let mut sorted = ts[..window].to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let median = |sorted: &[f64]| {
+        if window % 2 == 1 {
+            sorted[window / 2]
+        } else {
+            (sorted[window / 2 - 1] + sorted[window / 2]) / 2.
+        }
+    };
+
+    let mut medians = Vec::with_capacity(ts.len() - window + 1);
+    medians.push(median(&sorted));
+
+    for i in window..ts.len() {
+        let old = sorted.partition_point(|&x| x < ts[i - window]);
+        sorted.remove(old);
+        let new = sorted.partition_point(|&x| x < ts[i]);
+        sorted.insert(new, ts[i]);
+        medians.push(median(&sorted));
+    }
+
+    medians
+}
+
+/// Calculates the mean of each window of length `window` sliding over a vector of time series data.
+/// The output has length `ts.len() - window + 1`.
+pub fn rolling_mean(ts: &[f64], window: usize) -> Vec<f64> {
+    moving_average(ts, window, WindowKind::Simple)
+}
+
+/// The weighting applied to the points in each window by [moving_average].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowKind {
+    /// Equal weights for every point in the window.
+    Simple,
+    /// Weights that rise linearly to a peak in the middle of the window and then fall linearly.
+    Triangular,
+    /// Weights that decay exponentially with the age of the point, with smoothing factor
+    /// `alpha = 2 / (window + 1)`.
+    Exponential,
+}
+
+impl WindowKind {
+    /// Returns the weights for a window of length `window`, ordered from the oldest to the newest
+    /// point. The weights sum to 1.
+    pub fn weights(&self, window: usize) -> Vec<f64> {
+        let raw = match self {
+            WindowKind::Simple => vec![1.; window],
+            WindowKind::Triangular => (0..window)
+                .map(|i| std::cmp::min(i + 1, window - i) as f64)
+                .collect(),
+            WindowKind::Exponential => {
+                let alpha = 2. / (window as f64 + 1.);
+                (0..window)
+                    .map(|i| (1. - alpha).powi((window - 1 - i) as i32))
+                    .collect()
+            }
+        };
+        let total = raw.iter().sum::<f64>();
+        raw.iter().map(|w| w / total).collect()
+    }
+}
+
+/// Calculates the weighted moving average of each window of length `window` sliding over a vector
+/// of time series data, with the weights given by `kind`. The output has length
+/// `ts.len() - window + 1`, and element `i` is the average of `ts[i..i + window]`, so that the
+/// last element is the average of the most recent window.
+pub fn moving_average(ts: &[f64], window: usize, kind: WindowKind) -> Vec<f64> {
+    assert!(
+        window > 0 && window <= ts.len(),
+        "window must be positive and no longer than the time series"
+    );
+    let weights = kind.weights(window);
+    ts.windows(window)
+        .map(|w| w.iter().zip(&weights).map(|(x, wt)| x * wt).sum())
+        .collect()
+}
+
+/// Estimates the power spectral density of a vector of time series data with the periodogram,
+/// assuming that the points are equally spaced in time. Returns the frequencies (in cycles per
+/// sample, from 0 up to the Nyquist frequency of 0.5) and the power at each frequency, which is the
+/// squared magnitude of the discrete Fourier transform coefficient normalized by the length of the
+/// series.
+pub fn periodogram(ts: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let n = ts.len();
+    let nfreqs = n / 2 + 1;
+
+    let freqs = (0..nfreqs).map(|k| k as f64 / n as f64).collect();
+    let power = (0..nfreqs)
+        .map(|k| {
+            let (re, im) = ts.iter().enumerate().fold((0., 0.), |(re, im), (t, x)| {
+                let theta = -2. * PI * (k * t % n) as f64 / n as f64;
+                (re + x * theta.cos(), im + x * theta.sin())
+            });
+            (re * re + im * im) / n as f64
+        })
+        .collect();
+
+    (freqs, power)
+}
+
+/// Performs a classical additive decomposition of a vector of time series data with seasonality of
+/// the given `period` into trend, seasonal, and residual components, which sum back to the original
+/// series. Returns `(trend, seasonal, residual)`.
+///
+/// # Remarks
+/// The trend is the centered moving average over one period (a 2 x `period` moving average if
+/// `period` is even). Within half a period of either end of the series, the average is taken over
+/// the part of the window that lies inside the series. The seasonal component is the average of the
+/// detrended series at each position in the period, shifted to have zero mean over a period.
+pub fn decompose(ts: &[f64], period: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let n = ts.len();
+    assert!(
+        period > 1 && period < n,
+        "period must be greater than 1 and less than the length of the time series"
+    );
+
+    // weights of the centered moving average
+    let half = period / 2;
+    let weights = if period % 2 == 1 {
+        vec![1. / period as f64; period]
+    } else {
+        let mut w = vec![1. / period as f64; period + 1];
+        w[0] /= 2.;
+        w[period] /= 2.;
+        w
+    };
+
+    let trend = (0..n)
+        .map(|i| {
+            let lo = i.saturating_sub(half);
+            let hi = std::cmp::min(i + half, n - 1);
+            let (total, wsum) = (lo..=hi).fold((0., 0.), |(total, wsum), j| {
+                let w = weights[j + half - i];
+                (total + w * ts[j], wsum + w)
+            });
+            total / wsum
+        })
+        .collect::<Vec<_>>();
+
+    let mut seasonal_means = vec![0.; period];
+    let mut counts = vec![0; period];
+    for i in 0..n {
+        seasonal_means[i % period] += ts[i] - trend[i];
+        counts[i % period] += 1;
+    }
+    for (s, c) in seasonal_means.iter_mut().zip(&counts) {
+        *s /= *c as f64;
+    }
+    let offset = mean(&seasonal_means);
+
+    let seasonal = (0..n)
+        .map(|i| seasonal_means[i % period] - offset)
+        .collect::<Vec<_>>();
+    let residual = (0..n)
+        .map(|i| ts[i] - trend[i] - seasonal[i])
+        .collect::<Vec<_>>();
+
+    (trend, seasonal, residual)
+}
+
+/// Removes a linear trend from a vector of time series data by subtracting the least squares line
+/// fit against the index, assuming that the points are equally spaced in time.
+pub fn detrend(ts: &[f64]) -> Vec<f64> {
+    let n = ts.len() as f64;
+    let t_mean = (n - 1.) / 2.;
+    let ts_mean = mean(ts);
+
+    let (sxy, sxx) = ts
+        .iter()
+        .enumerate()
+        .fold((0., 0.), |(sxy, sxx), (t, x)| {
+            let dt = t as f64 - t_mean;
+            (sxy + dt * (x - ts_mean), sxx + dt * dt)
+        });
+    let slope = if sxx > 0. { sxy / sxx } else { 0. };
+
+    ts.iter()
+        .enumerate()
+        .map(|(t, x)| x - ts_mean - slope * (t as f64 - t_mean))
+        .collect()
+}
+
+/// Removes a constant trend from a vector of time series data by subtracting its mean.
+pub fn detrend_constant(ts: &[f64]) -> Vec<f64> {
+    let ts_mean = mean(ts);
+    ts.iter().map(|x| x - ts_mean).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::statistics::argmax;
     use approx_eq::assert_approx_eq;
 
+    #[test]
+    fn test_rolling_median() {
+        let mut ts = (0..50).map(|i| (i as f64 / 5.).sin()).collect::<Vec<_>>();
+        let clean = ts.clone();
+        ts[10] += 20.;
+        ts[30] -= 20.;
+
+        let window = 5;
+        let medians = rolling_median(&ts, window);
+        let means = ts
+            .windows(window)
+            .map(|w| w.iter().sum::<f64>() / window as f64)
+            .collect::<Vec<_>>();
+        assert_eq!(medians.len(), ts.len() - window + 1);
+
+        // every output near a spike is still close to the underlying signal for the median,
+        // but is pulled far away for the mean
+        for i in [8, 28] {
+            assert!((medians[i] - clean[i + window / 2]).abs() < 0.25);
+            assert!((means[i] - clean[i + window / 2]).abs() > 3.);
+        }
+        assert!(medians.iter().all(|m| m.abs() <= 1.));
+
+        assert_eq!(rolling_median(&[3., 1., 2., 5., 4.], 3), vec![2., 2., 4.]);
+        assert_eq!(rolling_median(&[3., 1., 2., 5.], 2), vec![2., 1.5, 3.5]);
+    }
+
+    #[test]
+    fn test_moving_average() {
+        let ts = (0..30)
+            .map(|i| (i as f64 / 3.).cos() + 0.1 * i as f64)
+            .collect::<Vec<_>>();
+        let window = 4;
+        let simple = moving_average(&ts, window, WindowKind::Simple);
+        assert_eq!(simple, rolling_mean(&ts, window));
+        assert_eq!(simple.len(), ts.len() - window + 1);
+        for (i, m) in simple.iter().enumerate() {
+            assert_approx_eq!(*m, mean(&ts[i..i + window]));
+        }
+
+        for &w in &[1, 4, 5, 10] {
+            for kind in &[
+                WindowKind::Simple,
+                WindowKind::Triangular,
+                WindowKind::Exponential,
+            ] {
+                assert_approx_eq!(kind.weights(w).iter().sum::<f64>(), 1.);
+            }
+        }
+        assert_eq!(
+            WindowKind::Triangular.weights(5),
+            vec![1. / 9., 2. / 9., 3. / 9., 2. / 9., 1. / 9.]
+        );
+
+        // exponential weights favour the newest points
+        let weights = WindowKind::Exponential.weights(5);
+        assert!(weights.windows(2).all(|w| w[0] < w[1]));
+
+        // a constant series is unchanged by any weighting
+        let constant = [2.5; 8];
+        for m in moving_average(&constant, 3, WindowKind::Exponential) {
+            assert_approx_eq!(m, 2.5);
+        }
+        assert_eq!(
+            moving_average(&[1., 2., 3., 4.], 3, WindowKind::Triangular),
+            vec![2., 3.]
+        );
+    }
+
+    #[test]
+    fn test_periodogram() {
+        let n = 128;
+        let ts = (0..n)
+            .map(|t| 3. * (2. * PI * 0.125 * t as f64).sin())
+            .collect::<Vec<_>>();
+        let (freqs, power) = periodogram(&ts);
+        assert_eq!(freqs.len(), n / 2 + 1);
+        assert_eq!(power.len(), n / 2 + 1);
+
+        let peak = argmax(&power);
+        assert_approx_eq!(freqs[peak], 0.125);
+        // all of the power is at the peak: n * amplitude^2 / 4
+        assert_approx_eq!(power[peak], n as f64 * 9. / 4.);
+        for (i, p) in power.iter().enumerate() {
+            if i != peak {
+                assert!(*p < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decompose() {
+        let period = 12;
+        let n = 240;
+        let true_trend = (0..n).map(|t| 2. + 0.05 * t as f64).collect::<Vec<_>>();
+        let true_seasonal = (0..n)
+            .map(|t| 3. * (2. * PI * t as f64 / period as f64).sin())
+            .collect::<Vec<_>>();
+        let ts = (0..n)
+            .map(|t| true_trend[t] + true_seasonal[t])
+            .collect::<Vec<_>>();
+
+        let (trend, seasonal, residual) = decompose(&ts, period);
+        assert_eq!(trend.len(), n);
+
+        for t in 0..n {
+            assert_approx_eq!(trend[t] + seasonal[t] + residual[t], ts[t]);
+            assert!((seasonal[t] - true_seasonal[t]).abs() < 0.1);
+        }
+        for t in period..(n - period) {
+            assert_approx_eq!(trend[t], true_trend[t]);
+            assert!(residual[t].abs() < 0.1);
+        }
+
+        let (trend, _, _) = decompose(&ts, 7);
+        assert_eq!(trend.len(), n);
+    }
+
+    #[test]
+    fn test_detrend() {
+        let line = (0..50).map(|t| 3. - 0.7 * t as f64).collect::<Vec<_>>();
+        for x in detrend(&line) {
+            assert!(x.abs() < 1e-10);
+        }
+
+        let ts = (0..100)
+            .map(|t| 1.5 + 0.2 * t as f64 + (t as f64 * 0.9).sin())
+            .collect::<Vec<_>>();
+        let detrended = detrend(&ts);
+        assert!(mean(&detrended).abs() < 1e-10);
+        // residuals are uncorrelated with the index
+        let slope = detrended
+            .iter()
+            .enumerate()
+            .map(|(t, x)| (t as f64 - 49.5) * x)
+            .sum::<f64>();
+        assert!(slope.abs() < 1e-8);
+
+        let centered = detrend_constant(&ts);
+        assert!(mean(&centered).abs() < 1e-10);
+        assert_approx_eq!(centered[10] - centered[0], ts[10] - ts[0]);
+    }
+
     #[test]
     fn test_acf_acovf() {
         let data: Vec<f64> = vec![
@@ -117,8 +490,11 @@ mod tests {
             1.39930070e-01,
         ];
 
+        let fast = acf_fft(&data, 49);
+        assert_eq!(fast.len(), 50);
         for i in 0..50 {
             assert_approx_eq!(acf(&data, i), autocorrelations[i as usize]);
+            assert_approx_eq!(fast[i as usize], acf(&data, i));
             assert_approx_eq!(acovf(&data, i) / acovf(&data, 0), acf(&data, i));
             assert!(acovf(&data, 0) >= acovf(&data, i).abs());
         }
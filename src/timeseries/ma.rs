@@ -0,0 +1,154 @@
+use super::AR;
+use crate::distributions::{Distribution1D, Normal};
+use crate::linalg::*;
+use crate::statistics::mean;
+use std::fmt::{Display, Formatter, Result};
+
+/// Implements a [moving-average model](https://en.wikipedia.org/wiki/Moving-average_model).
+#[derive(Debug)]
+pub struct MA {
+    pub q: usize,
+    pub coeffs: Vec<f64>,
+    pub intercept: f64,
+}
+
+/// Create a new [moving-average model](https://en.wikipedia.org/wiki/Moving-average_model) of
+/// order q.
+impl MA {
+    pub fn new(q: usize) -> Self {
+        assert!(q > 0, "q must be greater than 0");
+        MA {
+            q,
+            coeffs: vec![0.; q],
+            intercept: 0.,
+        }
+    }
+
+    /// Fit the MA(q) model to the data using the Hannan-Rissanen algorithm. A long autoregressive
+    /// model is first fit to estimate the innovations, and the coefficients are then found by least
+    /// squares regression of the data on the lagged innovations.
+    pub fn fit(&mut self, data: &[f64]) -> &mut Self {
+        let q = self.q;
+        // order of the long AR model
+        let m = std::cmp::max(2 * q, (data.len() as f64).ln().powi(2).ceil() as usize);
+        assert!(
+            data.len() > 2 * (m + q),
+            "not enough data points to fit a MA({}) model",
+            q
+        );
+
+        self.intercept = mean(data);
+        let adjusted = data
+            .iter()
+            .map(|x| x - self.intercept)
+            .collect::<Vec<f64>>();
+
+        let mut ar = AR::new(m);
+        ar.fit(&adjusted);
+        ar.intercept = 0.;
+        let innovations = (m..adjusted.len())
+            .map(|t| adjusted[t] - ar.predict_one(&adjusted[..t]))
+            .collect::<Vec<f64>>();
+
+        // regress x_t on e_{t-1}, ..., e_{t-q}
+        let nrows = innovations.len() - q;
+        let x = (q..innovations.len())
+            .flat_map(|t| (1..=q).map(move |i| t - i))
+            .map(|t| innovations[t])
+            .collect::<Vec<f64>>();
+        let y = &adjusted[(m + q)..];
+
+        let xty = matmul(&x, y, nrows, nrows, true, false);
+        self.coeffs = solve(&xtx(&x, nrows), &xty);
+        self
+    }
+
+    /// Estimate the innovations (one-step-ahead prediction errors) of the model for the given data,
+    /// assuming that the innovations before the start of the data are zero.
+    pub fn innovations(&self, data: &[f64]) -> Vec<f64> {
+        let mut e = vec![0.; data.len()];
+        for t in 0..data.len() {
+            e[t] = data[t]
+                - self.intercept
+                - (1..=std::cmp::min(self.q, t))
+                    .map(|i| self.coeffs[i - 1] * e[t - i])
+                    .sum::<f64>();
+        }
+        e
+    }
+
+    /// Predict n values ahead. Forecasts more than q steps ahead are equal to the mean.
+    pub fn predict(&self, data: &[f64], n: usize) -> Vec<f64> {
+        let e = self.innovations(data);
+        let len = e.len();
+        (1..=n)
+            .map(|h| {
+                self.intercept
+                    + (h..=self.q)
+                        .filter(|i| *i < len + h)
+                        .map(|i| self.coeffs[i - 1] * e[len + h - i - 1])
+                        .sum::<f64>()
+            })
+            .collect()
+    }
+
+    /// Simulate `n` values of a zero-mean MA(q) process with coefficients `params` and normally
+    /// distributed innovations with standard deviation `noise_std`, using the random seed `seed`.
+    pub fn simulate(params: &[f64], noise_std: f64, n: usize, seed: u64) -> Vec<f64> {
+        let q = params.len();
+        alea::set_seed(seed);
+        let e = Normal::new(0., noise_std).sample_n(n + q);
+        (q..(n + q))
+            .map(|t| e[t] + (1..=q).map(|i| params[i - 1] * e[t - i]).sum::<f64>())
+            .collect()
+    }
+}
+
+impl Display for MA {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "MA({}) model", self.q)?;
+        for (q, coeff) in self.coeffs.iter().enumerate() {
+            writeln!(f, "q{:.4} = {:.4}", q + 1, coeff)?;
+        }
+        writeln!(f, "intercept = {:.4}", self.intercept)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_ma_model() {
+        let data = MA::simulate(&[0.6], 1., 5000, 42);
+        assert_eq!(data.len(), 5000);
+
+        let mut ma = MA::new(1);
+        ma.fit(&data);
+        assert!((ma.coeffs[0] - 0.6).abs() < 0.05, "{}", ma.coeffs[0]);
+
+        let data = MA::simulate(&[0.5, -0.3], 2., 5000, 7)
+            .iter()
+            .map(|x| x + 10.)
+            .collect::<Vec<_>>();
+        let mut ma = MA::new(2);
+        ma.fit(&data);
+        assert!((ma.coeffs[0] - 0.5).abs() < 0.05, "{}", ma.coeffs[0]);
+        assert!((ma.coeffs[1] + 0.3).abs() < 0.05, "{}", ma.coeffs[1]);
+        assert!((ma.intercept - 10.).abs() < 0.2);
+
+        let pred = ma.predict(&data, 5);
+        let e = ma.innovations(&data);
+        let n = e.len();
+        assert_approx_eq!(
+            pred[0],
+            ma.intercept + ma.coeffs[0] * e[n - 1] + ma.coeffs[1] * e[n - 2]
+        );
+        assert_approx_eq!(pred[1], ma.intercept + ma.coeffs[1] * e[n - 1]);
+        for p in &pred[2..] {
+            assert_approx_eq!(*p, ma.intercept);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::distributions::{cumulative_weights, draw_index};
+use crate::linalg::{is_square, solve};
+
+/// Implements a discrete-time [Markov chain](https://en.wikipedia.org/wiki/Markov_chain) on a
+/// finite set of states `0..n_states`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarkovChain {
+    /// Row-major transition matrix, where entry `(i, j)` is the probability of moving from state
+    /// `i` to state `j`.
+    transition: Vec<f64>,
+    n_states: usize,
+}
+
+impl MarkovChain {
+    /// Create a new Markov chain with the given row-major transition matrix, where entry `(i, j)`
+    /// is the probability of moving from state `i` to state `j`.
+    ///
+    /// # Errors
+    /// Panics if the matrix is not square, or if it is not row-stochastic (i.e., if any entry is
+    /// negative or any row does not sum to 1).
+    pub fn new(transition: &[f64]) -> Self {
+        let n_states = is_square(transition).expect("transition matrix must be square");
+        assert!(n_states > 0, "transition matrix must not be empty");
+        assert!(
+            transition.iter().all(|&p| p >= 0.),
+            "transition probabilities must be non-negative"
+        );
+        assert!(
+            transition
+                .chunks(n_states)
+                .all(|row| (row.iter().sum::<f64>() - 1.).abs() < 1e-10),
+            "rows of the transition matrix must sum to 1"
+        );
+        Self {
+            transition: transition.to_vec(),
+            n_states,
+        }
+    }
+
+    /// Returns the number of states.
+    pub fn n_states(&self) -> usize {
+        self.n_states
+    }
+
+    /// Returns the row-major transition matrix.
+    pub fn transition(&self) -> &[f64] {
+        &self.transition
+    }
+
+    /// Take a single random step from `state`, using the random seed `seed`.
+    pub fn step(&self, state: usize, seed: u64) -> usize {
+        alea::set_seed(seed);
+        self.draw(state)
+    }
+
+    /// Simulate `n` states of the chain, starting with `start`, using the random seed `seed`.
+    pub fn simulate(&self, start: usize, n: usize, seed: u64) -> Vec<usize> {
+        alea::set_seed(seed);
+        let mut states = Vec::with_capacity(n);
+        let mut state = start;
+        for i in 0..n {
+            if i > 0 {
+                state = self.draw(state);
+            }
+            states.push(state);
+        }
+        states
+    }
+
+    /// Calculates the stationary distribution `pi` of the chain, which satisfies `pi P = pi`, where
+    /// `P` is the transition matrix, and sums to 1. This is found by solving for the left
+    /// eigenvector of `P` with eigenvalue 1.
+    ///
+    /// # Remarks
+    /// The stationary distribution is unique if the chain is irreducible. Otherwise, the linear
+    /// system is singular and the result is not meaningful.
+    pub fn stationary_distribution(&self) -> Vec<f64> {
+        let n = self.n_states;
+        // (P^T - I) pi = 0, with the last equation replaced by sum(pi) = 1
+        let mut a = vec![0.; n * n];
+        for i in 0..n {
+            for j in 0..n {
+                a[i * n + j] = self.transition[j * n + i] - if i == j { 1. } else { 0. };
+            }
+        }
+        for j in 0..n {
+            a[(n - 1) * n + j] = 1.;
+        }
+        let mut b = vec![0.; n];
+        b[n - 1] = 1.;
+        solve(&a, &b)
+    }
+
+    /// Draws the next state from the row of the transition matrix for `state`.
+    fn draw(&self, state: usize) -> usize {
+        assert!(state < self.n_states, "state out of range");
+        let row = &self.transition[state * self.n_states..(state + 1) * self.n_states];
+        draw_index(&cumulative_weights(row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_markov_chain() {
+        let chain = MarkovChain::new(&[0.5, 0.3, 0.2, 0.1, 0.8, 0.1, 0.4, 0., 0.6]);
+        assert_eq!(chain.n_states(), 3);
+
+        let pi = chain.stationary_distribution();
+        assert_approx_eq!(pi.iter().sum::<f64>(), 1.);
+        for j in 0..3 {
+            let next = (0..3).map(|i| pi[i] * chain.transition()[i * 3 + j]).sum();
+            assert_approx_eq!(pi[j], next);
+        }
+
+        let states = chain.simulate(2, 200000, 17);
+        assert_eq!(states.len(), 200000);
+        assert_eq!(states[0], 2);
+        for (j, p) in pi.iter().enumerate() {
+            let freq = states.iter().filter(|&&s| s == j).count() as f64 / states.len() as f64;
+            assert!((freq - p).abs() < 0.01);
+        }
+        // state 2 never moves to state 1
+        assert!(states.windows(2).all(|w| !(w[0] == 2 && w[1] == 1)));
+        assert_eq!(states, chain.simulate(2, 200000, 17));
+
+        let next = chain.step(1, 3);
+        assert!(next < 3);
+        assert_eq!(next, chain.step(1, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "rows of the transition matrix must sum to 1")]
+    fn test_markov_chain_not_stochastic() {
+        MarkovChain::new(&[0.5, 0.4, 0.5, 0.5]);
+    }
+}
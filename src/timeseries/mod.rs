@@ -2,6 +2,14 @@
 
 mod autoregressive;
 mod functions;
+mod ma;
+mod markov;
+mod simulate;
+mod unit_root;
 
 pub use autoregressive::*;
 pub use functions::*;
+pub use ma::*;
+pub use markov::*;
+pub use simulate::*;
+pub use unit_root::*;
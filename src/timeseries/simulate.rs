@@ -0,0 +1,118 @@
+use crate::distributions::{Distribution1D, Normal};
+
+/// Simulate a path of `n` values of [geometric Brownian
+/// motion](https://en.wikipedia.org/wiki/Geometric_Brownian_motion) with drift `mu` and volatility
+/// `sigma`, starting at `s0` and sampled every `dt`, using the random seed `seed`. Each step uses
+/// the exact solution `S(t + dt) = S(t) exp((mu - sigma^2 / 2) dt + sigma sqrt(dt) Z)`, where `Z`
+/// is standard normal, so there is no discretization error.
+///
+/// # Errors
+/// Panics if `s0` is not positive, if `sigma` is negative, or if `dt` is not positive.
+pub fn gbm(s0: f64, mu: f64, sigma: f64, dt: f64, n: usize, seed: u64) -> Vec<f64> {
+    assert!(s0 > 0., "s0 must be positive");
+    assert!(sigma >= 0., "sigma must be non-negative");
+    assert!(dt > 0., "dt must be positive");
+    if n == 0 {
+        return vec![];
+    }
+    alea::set_seed(seed);
+    let drift = (mu - sigma * sigma / 2.) * dt;
+    let z = Normal::new(0., 1.).sample_n(n - 1);
+    let mut path = Vec::with_capacity(n);
+    path.push(s0);
+    let mut s = s0;
+    for zi in z {
+        s *= (drift + sigma * dt.sqrt() * zi).exp();
+        path.push(s);
+    }
+    path
+}
+
+/// Simulate `n` values of a Gaussian [random walk](https://en.wikipedia.org/wiki/Random_walk)
+/// starting from 0, where each step is normally distributed with standard deviation `step_std`,
+/// using the random seed `seed`. The first value is the first step.
+pub fn random_walk(n: usize, step_std: f64, seed: u64) -> Vec<f64> {
+    alea::set_seed(seed);
+    Normal::new(0., step_std)
+        .sample_n(n)
+        .into_iter()
+        .scan(0., |x, step| {
+            *x += step;
+            Some(*x)
+        })
+        .collect()
+}
+
+/// Simulate `n` values of a zero-mean AR(1) process `x[t] = phi * x[t - 1] + e[t]`, where the
+/// innovations `e` are normally distributed with standard deviation `noise_std`, using the random
+/// seed `seed`. If the process is stationary (`|phi| < 1`), the first value is drawn from the
+/// stationary distribution, so no burn-in is needed; otherwise, the process starts from 0.
+pub fn simulate_ar1(phi: f64, noise_std: f64, n: usize, seed: u64) -> Vec<f64> {
+    alea::set_seed(seed);
+    let e = Normal::new(0., noise_std).sample_n(n);
+    let mut x = Vec::with_capacity(n);
+    for (t, et) in e.into_iter().enumerate() {
+        let xt = if t > 0 {
+            phi * x[t - 1] + et
+        } else if phi.abs() < 1. {
+            et / (1. - phi * phi).sqrt()
+        } else {
+            et
+        };
+        x.push(xt);
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::{mean, sample_std};
+    use crate::timeseries::acf;
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_gbm() {
+        let (mu, sigma, dt) = (0.1, 0.3, 1. / 252.);
+        let path = gbm(100., mu, sigma, dt, 100_001, 3);
+        assert_eq!(path.len(), 100_001);
+        assert_eq!(path[0], 100.);
+        assert!(path.iter().all(|s| *s > 0.));
+
+        let log_returns = path
+            .windows(2)
+            .map(|w| (w[1] / w[0]).ln())
+            .collect::<Vec<_>>();
+        let expected_std = sigma * dt.sqrt();
+        // within four standard errors of the mean
+        let standard_error = expected_std / 100_000_f64.sqrt();
+        assert!((mean(&log_returns) - (mu - sigma * sigma / 2.) * dt).abs() < 4. * standard_error);
+        assert_approx_eq!(sample_std(&log_returns), expected_std, 1e-2);
+
+        assert_eq!(path, gbm(100., mu, sigma, dt, 100_001, 3));
+        assert!(gbm(100., mu, sigma, dt, 0, 3).is_empty());
+    }
+
+    #[test]
+    fn test_random_walk() {
+        let walk = random_walk(1000, 2., 5);
+        assert_eq!(walk.len(), 1000);
+        assert_eq!(walk, random_walk(1000, 2., 5));
+
+        let steps = walk.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>();
+        assert_approx_eq!(sample_std(&steps), 2., 0.1);
+        assert!(random_walk(0, 1., 5).is_empty());
+    }
+
+    #[test]
+    fn test_simulate_ar1() {
+        for &phi in &[-0.5, 0.3, 0.9] {
+            let x = simulate_ar1(phi, 1., 20_000, 8);
+            assert_eq!(x.len(), 20_000);
+            assert!((acf(&x, 1) - phi).abs() < 0.03, "{}", acf(&x, 1));
+            // the stationary variance is noise_std^2 / (1 - phi^2)
+            assert_approx_eq!(sample_std(&x), 1. / (1. - phi * phi).sqrt(), 0.1);
+        }
+        assert_eq!(simulate_ar1(0.5, 1., 100, 1), simulate_ar1(0.5, 1., 100, 1));
+    }
+}
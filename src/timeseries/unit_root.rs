@@ -0,0 +1,108 @@
+use crate::distributions::Normal;
+use crate::linalg::{dot, invert_matrix, xtx};
+use crate::predict::ridge;
+
+/// Performs the [augmented Dickey-Fuller
+/// test](https://en.wikipedia.org/wiki/Augmented_Dickey%E2%80%93Fuller_test) for a unit root in a
+/// time series, with a constant and `lags` lagged differences in the regression
+///
+/// `dy[t] = a + g * y[t - 1] + d_1 * dy[t - 1] + ... + d_lags * dy[t - lags] + e[t]`.
+///
+/// Returns the test statistic (the t-statistic of `g`) and an approximate p-value, computed from
+/// [MacKinnon's](https://doi.org/10.2307/1391470) regression surface for the asymptotic
+/// distribution of the statistic. The null hypothesis is that the series has a unit root, so a
+/// small p-value is evidence that the series is stationary.
+///
+/// # Errors
+/// Panics if the series is too short to fit the regression.
+pub fn adf_test(ts: &[f64], lags: usize) -> (f64, f64) {
+    let dy = ts.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>();
+    let p = lags + 2;
+    assert!(
+        dy.len() > lags + p,
+        "time series is too short for the number of lags"
+    );
+    let nobs = dy.len() - lags;
+
+    let mut x = Vec::with_capacity(nobs * p);
+    for t in lags..dy.len() {
+        x.push(1.);
+        x.push(ts[t]);
+        x.extend((1..=lags).map(|i| dy[t - i]));
+    }
+    let y = &dy[lags..];
+
+    let coef = ridge(&x, y, 0., nobs, p);
+    let rss = x
+        .chunks(p)
+        .zip(y)
+        .map(|(row, yi)| (yi - dot(row, &coef)).powi(2))
+        .sum::<f64>();
+    let s2 = rss / (nobs - p) as f64;
+    let cov = invert_matrix(&xtx(&x, nobs));
+    let stat = coef[1] / (s2 * cov[p + 1]).sqrt();
+
+    (stat, mackinnon_pvalue(stat))
+}
+
+/// Approximates the p-value of a Dickey-Fuller test statistic (for a regression with a constant)
+/// with the polynomial approximations of MacKinnon (1994), as used by statsmodels.
+fn mackinnon_pvalue(stat: f64) -> f64 {
+    const TAU_MAX: f64 = 2.74;
+    const TAU_MIN: f64 = -18.83;
+    const TAU_STAR: f64 = -1.61;
+    const SMALL_P: [f64; 3] = [2.1659, 1.4412, 0.038269];
+    const LARGE_P: [f64; 4] = [1.7339, 0.93202, -0.12745, -0.010368];
+
+    if stat > TAU_MAX {
+        return 1.;
+    }
+    if stat < TAU_MIN {
+        return 0.;
+    }
+    let coef: &[f64] = if stat <= TAU_STAR { &SMALL_P } else { &LARGE_P };
+    let z = coef.iter().rev().fold(0., |acc, c| acc * stat + c);
+    Normal::new(0., 1.).cdf(z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::{random_walk, simulate_ar1};
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_mackinnon_pvalue() {
+        // the 1%, 5%, and 10% asymptotic critical values
+        assert_approx_eq!(mackinnon_pvalue(-3.43), 0.01, 0.05);
+        assert_approx_eq!(mackinnon_pvalue(-2.86), 0.05, 0.05);
+        assert_approx_eq!(mackinnon_pvalue(-2.57), 0.1, 0.05);
+        // continuous where the approximations meet
+        assert_approx_eq!(
+            mackinnon_pvalue(-1.61 - 1e-9),
+            mackinnon_pvalue(-1.61 + 1e-9),
+            1e-2
+        );
+        assert_eq!(mackinnon_pvalue(3.), 1.);
+        assert_eq!(mackinnon_pvalue(-20.), 0.);
+    }
+
+    #[test]
+    fn test_adf() {
+        let walk = random_walk(500, 1., 1);
+        let (stat, p) = adf_test(&walk, 2);
+        assert!(stat > -2.86 && p > 0.1, "{} {}", stat, p);
+
+        for seed in 0..5 {
+            let ar = simulate_ar1(0.5, 1., 500, seed);
+            let (stat, p) = adf_test(&ar, 2);
+            assert!(stat < -3.43 && p < 0.01, "{} {}", stat, p);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "too short")]
+    fn test_adf_too_short() {
+        adf_test(&[1., 2., 3., 4.], 2);
+    }
+}
@@ -0,0 +1,160 @@
+//! Metrics for evaluating classifiers.
+
+/// Computes the confusion matrix for a classification problem with `n_classes` classes, where the
+/// labels are integers in `0..n_classes`. The entry in row `i` and column `j` is the number of
+/// samples with true label `i` that were predicted to have label `j`.
+///
+/// # Errors
+/// Panics if `y_true` and `y_pred` have different lengths or if any label is out of range.
+pub fn confusion_matrix_multiclass(
+    y_true: &[usize],
+    y_pred: &[usize],
+    n_classes: usize,
+) -> Vec<Vec<usize>> {
+    assert_eq!(
+        y_true.len(),
+        y_pred.len(),
+        "y_true and y_pred must have the same length"
+    );
+    let mut cm = vec![vec![0; n_classes]; n_classes];
+    for (&t, &p) in y_true.iter().zip(y_pred) {
+        assert!(t < n_classes && p < n_classes, "label out of range");
+        cm[t][p] += 1;
+    }
+    cm
+}
+
+/// Calculates the precision of each class from a confusion matrix (as returned by
+/// [confusion_matrix_multiclass]). Classes that are never predicted have a precision of 0.
+pub fn precision_per_class(cm: &[Vec<usize>]) -> Vec<f64> {
+    (0..cm.len())
+        .map(|j| {
+            let predicted = cm.iter().map(|row| row[j]).sum::<usize>();
+            safe_ratio(cm[j][j], predicted)
+        })
+        .collect()
+}
+
+/// Calculates the recall of each class from a confusion matrix (as returned by
+/// [confusion_matrix_multiclass]). Classes that never occur have a recall of 0.
+pub fn recall_per_class(cm: &[Vec<usize>]) -> Vec<f64> {
+    cm.iter()
+        .enumerate()
+        .map(|(i, row)| safe_ratio(row[i], row.iter().sum()))
+        .collect()
+}
+
+/// Calculates the macro-averaged precision, which is the unweighted mean of the precision of each
+/// class.
+pub fn macro_precision(cm: &[Vec<usize>]) -> f64 {
+    let precisions = precision_per_class(cm);
+    precisions.iter().sum::<f64>() / precisions.len() as f64
+}
+
+/// Calculates the macro-averaged recall, which is the unweighted mean of the recall of each class.
+pub fn macro_recall(cm: &[Vec<usize>]) -> f64 {
+    let recalls = recall_per_class(cm);
+    recalls.iter().sum::<f64>() / recalls.len() as f64
+}
+
+/// Calculates the micro-averaged precision, which pools the true and false positives of all of the
+/// classes. For single-label problems, this is the same as the accuracy.
+pub fn micro_precision(cm: &[Vec<usize>]) -> f64 {
+    let correct = (0..cm.len()).map(|i| cm[i][i]).sum();
+    safe_ratio(correct, cm.iter().flatten().sum())
+}
+
+/// Calculates the micro-averaged recall, which pools the true positives and false negatives of all
+/// of the classes. For single-label problems, this is the same as the micro-averaged precision
+/// (and the accuracy).
+pub fn micro_recall(cm: &[Vec<usize>]) -> f64 {
+    micro_precision(cm)
+}
+
+/// Calculates the [Matthews correlation
+/// coefficient](https://en.wikipedia.org/wiki/Phi_coefficient) for a binary classification
+/// problem, where the labels are `0.` or `1.`. This is `1` for perfect predictions, `-1` for
+/// predictions that are always wrong, and around `0` for random predictions, even when the classes
+/// are imbalanced. Returns `0` if any of the row or column sums of the confusion matrix are zero.
+///
+/// # Errors
+/// Panics if `y_true` and `y_pred` have different lengths or if any label is not `0.` or `1.`.
+pub fn matthews_corrcoef(y_true: &[f64], y_pred: &[f64]) -> f64 {
+    assert_eq!(
+        y_true.len(),
+        y_pred.len(),
+        "y_true and y_pred must have the same length"
+    );
+    let (mut tp, mut tn, mut fp, mut fn_) = (0_f64, 0., 0., 0.);
+    for (&t, &p) in y_true.iter().zip(y_pred) {
+        assert!(
+            (t == 0. || t == 1.) && (p == 0. || p == 1.),
+            "labels must be 0 or 1"
+        );
+        match (t == 1., p == 1.) {
+            (true, true) => tp += 1.,
+            (false, false) => tn += 1.,
+            (false, true) => fp += 1.,
+            (true, false) => fn_ += 1.,
+        }
+    }
+    let denom = (tp + fp) * (tp + fn_) * (tn + fp) * (tn + fn_);
+    if denom == 0. {
+        0.
+    } else {
+        (tp * tn - fp * fn_) / denom.sqrt()
+    }
+}
+
+fn safe_ratio(num: usize, denom: usize) -> f64 {
+    if denom == 0 {
+        0.
+    } else {
+        num as f64 / denom as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{Bernoulli, Distribution1D};
+    use approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_confusion_matrix_multiclass() {
+        let y_true = [0, 0, 0, 1, 1, 1, 1, 2, 2, 2];
+        let y_pred = [0, 1, 0, 1, 1, 2, 1, 2, 1, 2];
+        let cm = confusion_matrix_multiclass(&y_true, &y_pred, 3);
+        assert_eq!(cm, vec![vec![2, 1, 0], vec![0, 3, 1], vec![0, 1, 2]]);
+
+        assert_eq!(precision_per_class(&cm), vec![1., 0.6, 2. / 3.]);
+        assert_eq!(recall_per_class(&cm), vec![2. / 3., 0.75, 2. / 3.]);
+        assert_approx_eq!(macro_precision(&cm), 34. / 45.);
+        assert_approx_eq!(macro_recall(&cm), 25. / 36.);
+        assert_approx_eq!(micro_precision(&cm), 0.7);
+        assert_approx_eq!(micro_recall(&cm), 0.7);
+
+        // a class that never appears
+        let cm = confusion_matrix_multiclass(&[0, 1], &[0, 1], 3);
+        assert_eq!(precision_per_class(&cm), vec![1., 1., 0.]);
+        assert_eq!(recall_per_class(&cm), vec![1., 1., 0.]);
+    }
+
+    #[test]
+    fn test_matthews_corrcoef() {
+        let y = [1., 0., 0., 1., 0., 0., 0., 1.];
+        let flipped = y.iter().map(|x| 1. - x).collect::<Vec<_>>();
+        assert_approx_eq!(matthews_corrcoef(&y, &y), 1.);
+        assert_approx_eq!(matthews_corrcoef(&y, &flipped), -1.);
+        assert_eq!(matthews_corrcoef(&y, &[1.; 8]), 0.);
+
+        // tp = 2, tn = 3, fp = 2, fn = 1
+        let pred = [1., 1., 0., 0., 0., 1., 0., 1.];
+        assert_approx_eq!(matthews_corrcoef(&y, &pred), 2. / 60_f64.sqrt());
+
+        alea::set_seed(9);
+        let y = Bernoulli::new(0.2).sample_n(10000);
+        let pred = Bernoulli::new(0.5).sample_n(10000);
+        assert!(matthews_corrcoef(&y, &pred).abs() < 0.05);
+    }
+}
@@ -1,5 +1,7 @@
 //! Methods for validating models.
 
+mod metrics;
 mod resample;
 
+pub use metrics::*;
 pub use resample::*;
@@ -58,6 +58,59 @@ pub fn shuffle_two(arr1: &[f64], arr2: &[f64]) -> (Vec<f64>, Vec<f64>) {
     (shuf1, shuf2)
 }
 
+/// Splits the indices of `labels` into `k` folds for cross-validation, such that each class is
+/// spread as evenly as possible across the folds. The indices of each class are shuffled (with a
+/// Fisher-Yates shuffle, using the random seed `seed`) and then dealt out to the folds in turn.
+/// Returns a `(train, test)` pair of sorted index vectors for each fold.
+///
+/// # Errors
+/// Panics if `k < 2` or if `k` is larger than the number of labels.
+pub fn stratified_kfold(labels: &[f64], k: usize, seed: u64) -> Vec<(Vec<usize>, Vec<usize>)> {
+    assert!(k >= 2, "k must be at least 2");
+    assert!(
+        k <= labels.len(),
+        "k must not be larger than the number of samples"
+    );
+    alea::set_seed(seed);
+
+    let mut order = (0..labels.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| labels[a].partial_cmp(&labels[b]).unwrap());
+
+    let mut folds = vec![vec![]; k];
+    let mut fold = 0;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end < order.len() && labels[order[end]] == labels[order[start]] {
+            end += 1;
+        }
+        let class = &mut order[start..end];
+        for i in (1..class.len()).rev() {
+            let j = alea::u64_less_than(i as u64 + 1) as usize;
+            class.swap(i, j);
+        }
+        // carry on dealing from where the previous class stopped so fold sizes stay balanced
+        for &idx in class.iter() {
+            folds[fold].push(idx);
+            fold = (fold + 1) % k;
+        }
+        start = end;
+    }
+
+    (0..k)
+        .map(|i| {
+            let mut test = folds[i].clone();
+            test.sort_unstable();
+            let mut train = (0..k)
+                .filter(|&j| j != i)
+                .flat_map(|j| folds[j].iter().copied())
+                .collect::<Vec<_>>();
+            train.sort_unstable();
+            (train, test)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,6 +141,30 @@ mod tests {
         assert_approx_eq!(std(&x), mean(&stds), 0.025);
     }
 
+    #[test]
+    fn test_stratified_kfold() {
+        // 15% positive labels
+        let labels = (0..200)
+            .map(|i| if i % 20 < 3 { 1. } else { 0. })
+            .collect::<Vec<_>>();
+        let folds = stratified_kfold(&labels, 5, 42);
+        assert_eq!(folds.len(), 5);
+
+        let mut seen = vec![0; labels.len()];
+        for (train, test) in &folds {
+            assert_eq!(test.len(), 40);
+            assert_eq!(train.len() + test.len(), labels.len());
+            assert!(test.iter().all(|i| train.binary_search(i).is_err()));
+            test.iter().for_each(|&i| seen[i] += 1);
+
+            let positive = test.iter().filter(|&&i| labels[i] == 1.).count();
+            assert!((positive as f64 / test.len() as f64 - 0.15).abs() < 0.03);
+        }
+        assert!(seen.iter().all(|&c| c == 1));
+
+        assert_eq!(folds, stratified_kfold(&labels, 5, 42));
+    }
+
     #[test]
     fn test_jackknife_size() {
         let x = Normal::default().sample_n(50);